@@ -0,0 +1,46 @@
+use std::{env, io, path::PathBuf};
+
+/// What the I/O natives are allowed to touch. Everything is denied by default; the CLI
+/// grants standard input, the environment and the whole file system.
+#[derive(Debug, Default, Clone)]
+pub struct Capabilities {
+    pub stdin: bool,
+    /// Whether `env` and `set_env` may read and change environment variables.
+    pub environment: bool,
+    /// The directories whose contents the file natives may read and write.
+    pub file_roots: Vec<PathBuf>,
+}
+
+impl Capabilities {
+    /// Everything the CLI allows.
+    pub fn all() -> Self {
+        Capabilities {
+            stdin: true,
+            environment: true,
+            file_roots: vec![PathBuf::from("/")],
+        }
+    }
+
+    /// Resolves `path` against the working directory, following symbolic links, and checks
+    /// that it lies within one of the allowed roots. The file itself need not exist, but its
+    /// directory must.
+    pub fn check_path(&self, path: &str) -> io::Result<Option<PathBuf>> {
+        let path = env::current_dir()?.join(path);
+
+        let resolved = match path.canonicalize() {
+            Ok(resolved) => resolved,
+            Err(_) => match (path.parent(), path.file_name()) {
+                (Some(parent), Some(name)) => parent.canonicalize()?.join(name),
+                _ => return Ok(None),
+            },
+        };
+
+        let allowed = self
+            .file_roots
+            .iter()
+            .filter_map(|root| root.canonicalize().ok())
+            .any(|root| resolved.starts_with(root));
+
+        Ok(allowed.then_some(resolved))
+    }
+}
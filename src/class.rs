@@ -0,0 +1,132 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use crate::{
+    Callable, ExecuteInterpreterResult, Interpreter, LoxFunction, MethodKind, Token, Value,
+};
+
+/// The value a `class` declaration binds its name to.
+#[derive(Debug)]
+pub struct LoxClass {
+    pub name: String,
+    pub superclass: Option<Rc<LoxClass>>,
+    pub methods: HashMap<String, Rc<LoxFunction>>,
+    pub getters: HashMap<String, Rc<LoxFunction>>,
+    pub setters: HashMap<String, Rc<LoxFunction>>,
+    /// Methods called on the class itself, with the class as `this`.
+    pub statics: HashMap<String, Rc<LoxFunction>>,
+    /// The traits given after `with`, whose methods were copied into the tables above.
+    pub traits: Vec<Rc<LoxTrait>>,
+}
+
+type MethodTable = fn(&LoxClass) -> &HashMap<String, Rc<LoxFunction>>;
+
+impl LoxClass {
+    pub fn new(name: String, superclass: Option<Rc<LoxClass>>) -> Self {
+        LoxClass {
+            name,
+            superclass,
+            methods: HashMap::new(),
+            getters: HashMap::new(),
+            setters: HashMap::new(),
+            statics: HashMap::new(),
+            traits: Vec::new(),
+        }
+    }
+
+    /// The table holding methods of the given kind.
+    pub fn table_mut(&mut self, kind: MethodKind) -> &mut HashMap<String, Rc<LoxFunction>> {
+        match kind {
+            MethodKind::Method => &mut self.methods,
+            MethodKind::Getter => &mut self.getters,
+            MethodKind::Setter => &mut self.setters,
+            MethodKind::Static => &mut self.statics,
+        }
+    }
+
+    /// Whether this class or one of its superclasses was declared `with` the trait.
+    pub fn implements(&self, r#trait: &Rc<LoxTrait>) -> bool {
+        self.traits.iter().any(|other| Rc::ptr_eq(other, r#trait))
+            || self
+                .superclass
+                .as_ref()
+                .is_some_and(|superclass| superclass.implements(r#trait))
+    }
+
+    pub fn find_method(&self, name: &str) -> Option<Rc<LoxFunction>> {
+        self.find(name, |class| &class.methods)
+    }
+
+    pub fn find_getter(&self, name: &str) -> Option<Rc<LoxFunction>> {
+        self.find(name, |class| &class.getters)
+    }
+
+    pub fn find_setter(&self, name: &str) -> Option<Rc<LoxFunction>> {
+        self.find(name, |class| &class.setters)
+    }
+
+    pub fn find_static(&self, name: &str) -> Option<Rc<LoxFunction>> {
+        self.find(name, |class| &class.statics)
+    }
+
+    /// Looks `name` up in one of the method tables of this class, then of its superclasses.
+    fn find(&self, name: &str, table: MethodTable) -> Option<Rc<LoxFunction>> {
+        match table(self).get(name) {
+            Some(method) => Some(method.clone()),
+            None => self.superclass.as_ref()?.find(name, table),
+        }
+    }
+}
+
+/// Calling a class creates an instance and runs its `init` method on the arguments.
+impl Callable for Rc<LoxClass> {
+    fn arity(&self) -> usize {
+        self.find_method("init").map_or(0, |init| init.arity())
+    }
+
+    fn max_arity(&self) -> Option<usize> {
+        self.find_method("init")
+            .map_or(Some(0), |init| init.max_arity())
+    }
+
+    fn call(
+        &self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Value>,
+        token: Token,
+    ) -> ExecuteInterpreterResult {
+        let instance = Value::Instance(Rc::new(LoxInstance {
+            class: self.clone(),
+            fields: RefCell::new(HashMap::new()),
+        }));
+
+        if let Some(init) = self.find_method("init") {
+            init.bind(instance.clone())
+                .call(interpreter, arguments, token)?;
+        }
+
+        Ok(Some(instance))
+    }
+
+    fn as_str(&self) -> String {
+        self.name.clone()
+    }
+}
+
+/// The value a `trait` declaration binds its name to.
+#[derive(Debug)]
+pub struct LoxTrait {
+    pub name: String,
+    pub methods: Vec<(MethodKind, Rc<LoxFunction>)>,
+}
+
+#[derive(Debug)]
+pub struct LoxInstance {
+    pub class: Rc<LoxClass>,
+    pub fields: RefCell<HashMap<String, Value>>,
+}
+
+impl LoxInstance {
+    pub fn field(&self, name: &str) -> Option<Value> {
+        self.fields.borrow().get(name).cloned()
+    }
+}
@@ -0,0 +1,87 @@
+use std::{
+    cell::Cell,
+    fmt::Debug,
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// The time source behind the clock natives, replaceable so that output depending on the
+/// time can be reproduced.
+pub trait Clock: Debug {
+    /// The time elapsed since the Unix epoch.
+    fn now(&self) -> Duration;
+    /// The time elapsed since an arbitrary fixed point, never going backwards.
+    fn monotonic(&self) -> Duration;
+    fn sleep(&self, duration: Duration);
+}
+
+/// The operating system's clocks.
+#[derive(Debug)]
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        SystemClock {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        // A system clock set before 1970 is reported as the epoch itself.
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+    }
+
+    fn monotonic(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// A clock that stands still at a chosen time, moving only when slept on or advanced.
+#[derive(Debug)]
+pub struct FakeClock {
+    start: Duration,
+    elapsed: Cell<Duration>,
+}
+
+impl FakeClock {
+    pub fn new(start: Duration) -> Self {
+        FakeClock {
+            start,
+            elapsed: Cell::new(Duration::ZERO),
+        }
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.elapsed.set(self.elapsed.get() + duration);
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        self.start + self.elapsed.get()
+    }
+
+    fn monotonic(&self) -> Duration {
+        self.elapsed.get()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
@@ -0,0 +1,64 @@
+use std::rc::Rc;
+
+use crate::{Callable, ExecuteInterpreterResult, Interpreter, Token, Value};
+
+/// The value an `enum` declaration binds its name to.
+#[derive(Debug, PartialEq)]
+pub struct LoxEnum {
+    pub name: String,
+    /// Each variant's name and the names of its payload fields.
+    pub variants: Vec<(String, Vec<String>)>,
+}
+
+/// A value of one of an enum's variants, such as `State.Done(3)`.
+#[derive(Debug, PartialEq)]
+pub struct LoxVariant {
+    pub enumeration: Rc<LoxEnum>,
+    pub tag: String,
+    pub values: Vec<Value>,
+}
+
+impl LoxVariant {
+    /// Looks up a payload value by the field name given in the declaration.
+    pub fn field(&self, name: &str) -> Option<Value> {
+        let (_, fields) = self
+            .enumeration
+            .variants
+            .iter()
+            .find(|(tag, _)| *tag == self.tag)?;
+
+        let index = fields.iter().position(|field| field == name)?;
+        self.values.get(index).cloned()
+    }
+}
+
+/// The constructor of a variant with a payload, such as `State.Done`.
+#[derive(Debug)]
+pub struct VariantConstructor {
+    pub enumeration: Rc<LoxEnum>,
+    pub tag: String,
+    pub arity: usize,
+}
+
+impl Callable for VariantConstructor {
+    fn arity(&self) -> usize {
+        self.arity
+    }
+
+    fn call(
+        &self,
+        _: &mut Interpreter,
+        arguments: Vec<Value>,
+        _: Token,
+    ) -> ExecuteInterpreterResult {
+        Ok(Some(Value::Variant(Rc::new(LoxVariant {
+            enumeration: self.enumeration.clone(),
+            tag: self.tag.clone(),
+            values: arguments,
+        }))))
+    }
+
+    fn as_str(&self) -> String {
+        format!("<fn {}.{}>", self.enumeration.name, self.tag)
+    }
+}
@@ -1,6 +1,10 @@
 use {
-    crate::{EvaluateInterpreterResult, InterpreterError, Token, Value},
-    std::{cell::RefCell, collections::HashMap, rc::Rc},
+    crate::{ErrorKind, EvaluateInterpreterResult, InterpreterError, Token, Value},
+    std::{
+        cell::RefCell,
+        collections::{HashMap, HashSet},
+        rc::Rc,
+    },
 };
 
 // Thanks https://github.com/Pvlerick/codecrafters-interpreter-rust/blob/master/src/environment.rs
@@ -10,6 +14,12 @@ pub struct Environment {
     inner: Rc<RefCell<Inner>>,
 }
 
+impl Default for Environment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Environment {
     pub fn new() -> Self {
         Self {
@@ -27,6 +37,21 @@ impl Environment {
         self.inner.borrow_mut().define(name, value);
     }
 
+    pub fn define_constant(&mut self, name: String, value: Value) {
+        self.inner.borrow_mut().define_constant(name, value);
+    }
+
+    /// Defines a name declared by the script, which may not redeclare a constant of the
+    /// same scope.
+    pub fn declare(
+        &mut self,
+        name: &Token,
+        value: Value,
+        constant: bool,
+    ) -> Result<(), InterpreterError> {
+        self.inner.borrow_mut().declare(name, value, constant)
+    }
+
     pub fn assign(&mut self, name: &Token, value: &Value) -> Result<(), InterpreterError> {
         self.inner.borrow_mut().assign(name, value)
     }
@@ -34,12 +59,24 @@ impl Environment {
     pub fn get(&self, name: &Token) -> EvaluateInterpreterResult {
         self.inner.borrow_mut().get(name)
     }
+
+    /// Looks `name` up in this scope only, ignoring enclosing scopes.
+    pub fn get_local(&self, name: &str) -> Option<Value> {
+        self.inner.borrow().values.get(name).cloned()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Inner {
     enclosing: Option<Rc<RefCell<Inner>>>,
     values: HashMap<String, Value>,
+    constants: HashSet<String>,
+}
+
+impl Default for Inner {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Inner {
@@ -47,6 +84,7 @@ impl Inner {
         Inner {
             enclosing: None,
             values: HashMap::new(),
+            constants: HashSet::new(),
         }
     }
 
@@ -54,6 +92,7 @@ impl Inner {
         Self {
             enclosing: Some(inner.clone()),
             values: HashMap::new(),
+            constants: HashSet::new(),
         }
     }
 
@@ -61,8 +100,45 @@ impl Inner {
         self.values.insert(name, value);
     }
 
+    pub fn define_constant(&mut self, name: String, value: Value) {
+        self.constants.insert(name.clone());
+        self.values.insert(name, value);
+    }
+
+    pub fn declare(
+        &mut self,
+        name: &Token,
+        value: Value,
+        constant: bool,
+    ) -> Result<(), InterpreterError> {
+        let lexeme = &name.lexeme;
+        if self.constants.contains(lexeme) {
+            return Err(InterpreterError {
+                token: Some(name.clone()),
+                message: format!("Cannot redeclare constant '{lexeme}'."),
+                kind: ErrorKind::Runtime,
+            });
+        }
+
+        if constant {
+            self.define_constant(lexeme.clone(), value);
+        } else {
+            self.define(lexeme.clone(), value);
+        }
+
+        Ok(())
+    }
+
     pub fn assign(&mut self, name: &Token, value: &Value) -> Result<(), InterpreterError> {
         let lexeme = &name.lexeme;
+        if self.constants.contains(lexeme) {
+            return Err(InterpreterError {
+                token: Some(name.clone()),
+                message: format!("Cannot assign to constant '{lexeme}'."),
+                kind: ErrorKind::Runtime,
+            });
+        }
+
         if self.values.contains_key(lexeme) {
             self.values.insert(lexeme.clone(), value.clone());
             return Ok(());
@@ -75,6 +151,7 @@ impl Inner {
         Err(InterpreterError {
             token: Some(name.clone()),
             message: format!("Undefined variable '{lexeme}'."),
+            kind: ErrorKind::Runtime,
         })
     }
 
@@ -91,6 +168,7 @@ impl Inner {
         Err(InterpreterError {
             token: Some(name.clone()),
             message: format!("Undefined variable '{lexeme}'."),
+            kind: ErrorKind::Runtime,
         })
     }
 }
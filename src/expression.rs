@@ -30,6 +30,57 @@ pub enum Expression {
         parenthesis: Token,
         arguments: Vec<Expression>,
     },
+    Conditional {
+        condition: Box<Expression>,
+        then_branch: Box<Expression>,
+        else_branch: Box<Expression>,
+    },
+    Get {
+        object: Box<Expression>,
+        name: Token,
+    },
+    Set {
+        object: Box<Expression>,
+        name: Token,
+        right: Box<Expression>,
+    },
+    /// `object.name += right` and the like, which evaluate `object` once.
+    CompoundSet {
+        object: Box<Expression>,
+        name: Token,
+        operator: Token,
+        right: Box<Expression>,
+    },
+    List(Vec<Expression>),
+    Map(Vec<(String, Expression)>),
+    Spread {
+        operator: Token,
+        right: Box<Expression>,
+    },
+    Index {
+        object: Box<Expression>,
+        bracket: Token,
+        index: Box<Expression>,
+    },
+    SetIndex {
+        object: Box<Expression>,
+        bracket: Token,
+        index: Box<Expression>,
+        right: Box<Expression>,
+    },
+    /// `object[index] += right` and the like, which evaluate `object` and `index` once.
+    CompoundSetIndex {
+        object: Box<Expression>,
+        bracket: Token,
+        index: Box<Expression>,
+        operator: Token,
+        right: Box<Expression>,
+    },
+    This(Token),
+    Super {
+        keyword: Token,
+        method: Token,
+    },
 }
 
 impl fmt::Display for Expression {
@@ -47,6 +98,30 @@ impl fmt::Display for Expression {
             Expression::Assign { name, right } => write!(f, "(assign {} {right})", name.lexeme),
             Expression::Logical { left, operator, right } => write!(f, "(logical {} {left} {right})", operator.lexeme),
             Expression::Call { callee, parenthesis, arguments } => write!(f, "(call {callee} {parenthesis} {arguments:?})"),
+            Expression::Conditional { condition, then_branch, else_branch } => write!(f, "(conditional {condition} {then_branch} {else_branch})"),
+            Expression::Get { object, name } => write!(f, "(get {object} {})", name.lexeme),
+            Expression::List(elements) => {
+                write!(f, "(list")?;
+                for element in elements {
+                    write!(f, " {element}")?;
+                }
+                write!(f, ")")
+            }
+            Expression::Set { object, name, right } => write!(f, "(set {object} {} {right})", name.lexeme),
+            Expression::CompoundSet { object, name, operator, right } => write!(f, "(set {object} {} ({} (get {object} {}) {right}))", name.lexeme, operator.lexeme, name.lexeme),
+            Expression::Map(entries) => {
+                write!(f, "(map")?;
+                for (key, value) in entries {
+                    write!(f, " {key:?} {value}")?;
+                }
+                write!(f, ")")
+            }
+            Expression::Spread { right, .. } => write!(f, "(... {right})"),
+            Expression::Index { object, index, .. } => write!(f, "(index {object} {index})"),
+            Expression::SetIndex { object, index, right, .. } => write!(f, "(set-index {object} {index} {right})"),
+            Expression::CompoundSetIndex { object, index, operator, right, .. } => write!(f, "(set-index {object} {index} ({} (index {object} {index}) {right}))", operator.lexeme),
+            Expression::This(_) => write!(f, "this"),
+            Expression::Super { method, .. } => write!(f, "(super {})", method.lexeme),
         }
     }
 }
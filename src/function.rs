@@ -1,7 +1,17 @@
-use crate::{Environment, ExecuteInterpreterResult, Interpreter, Statement, Token, Value};
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    Environment, ExecuteInterpreterResult, Generator, Interpreter, Parameter, Statement, Token,
+    Value,
+};
 
 pub trait Callable: std::fmt::Debug {
+    /// The number of arguments that must be passed.
     fn arity(&self) -> usize;
+    /// The number of arguments that may be passed, or `None` if there is no limit.
+    fn max_arity(&self) -> Option<usize> {
+        Some(self.arity())
+    }
     fn call(
         &self,
         interpreter: &mut Interpreter,
@@ -14,20 +24,50 @@ pub trait Callable: std::fmt::Debug {
 #[derive(Debug, PartialEq)]
 pub struct LoxFunction {
     pub name: Token,
-    pub parameters: Vec<Token>,
+    pub parameters: Vec<Parameter>,
+    pub rest: Option<Token>,
     pub body: Vec<Statement>,
     pub closure: Environment,
+    pub generator: bool,
+    /// Whether this is a class's `init` method, which always returns the instance.
+    pub initializer: bool,
 }
 
 impl LoxFunction {
     pub fn get_name(&self) -> &str {
         &self.name.lexeme
     }
+
+    /// A copy of this method whose body sees `instance` as `this`.
+    pub fn bind(&self, instance: Value) -> LoxFunction {
+        let mut closure = self.closure.enclose();
+        closure.define("this".into(), instance);
+
+        LoxFunction {
+            name: self.name.clone(),
+            parameters: self.parameters.clone(),
+            rest: self.rest.clone(),
+            body: self.body.clone(),
+            closure,
+            generator: self.generator,
+            initializer: self.initializer,
+        }
+    }
 }
 
 impl super::Callable for LoxFunction {
     fn arity(&self) -> usize {
-        self.parameters.len()
+        self.parameters
+            .iter()
+            .filter(|parameter| parameter.default.is_none())
+            .count()
+    }
+
+    fn max_arity(&self) -> Option<usize> {
+        match self.rest {
+            Some(_) => None,
+            None => Some(self.parameters.len()),
+        }
     }
 
     fn call(
@@ -37,12 +77,41 @@ impl super::Callable for LoxFunction {
         _: Token,
     ) -> ExecuteInterpreterResult {
         let mut environment = self.closure.enclose();
+        let mut arguments = arguments.into_iter();
 
-        for (parameter, value) in self.parameters.iter().zip(arguments.into_iter()) {
-            environment.define(parameter.lexeme.clone(), value);
+        for parameter in &self.parameters {
+            let value = match (arguments.next(), &parameter.default) {
+                (Some(value), _) => value,
+                // Defaults see the closure and the parameters before them.
+                (None, Some(default)) => {
+                    interpreter.evaluate_in(default.clone(), environment.clone())?
+                }
+                (None, None) => Value::Nil,
+            };
+
+            environment.define(parameter.name.lexeme.clone(), value);
+        }
+
+        if let Some(rest) = &self.rest {
+            environment.define(
+                rest.lexeme.clone(),
+                Value::List(Rc::new(RefCell::new(arguments.collect()))),
+            );
+        }
+
+        if self.generator {
+            let generator =
+                Generator::new(self.name.lexeme.clone(), self.body.clone(), environment);
+
+            return Ok(Some(Value::Generator(Rc::new(RefCell::new(generator)))));
         }
 
         let returned = interpreter.execute_block(self.body.clone(), environment)?;
+
+        if self.initializer {
+            return Ok(self.closure.get_local("this"));
+        }
+
         Ok(returned)
     }
 
@@ -52,8 +121,39 @@ impl super::Callable for LoxFunction {
 }
 
 pub mod native {
-    use crate::{ExecuteInterpreterResult, Interpreter, InterpreterError, Token, Value};
-    use std::time::{SystemTime, UNIX_EPOCH};
+    use crate::{EvaluateInterpreterResult, ExecuteInterpreterResult, Interpreter, Token, Value};
+
+    /// A native function implemented by a plain Rust function over its arguments.
+    #[derive(Debug, Clone)]
+    pub struct NativeFunction {
+        pub name: &'static str,
+        pub arity: usize,
+        pub max_arity: Option<usize>,
+        pub function: fn(&mut Interpreter, Vec<Value>, &Token) -> EvaluateInterpreterResult,
+    }
+
+    impl super::Callable for NativeFunction {
+        fn arity(&self) -> usize {
+            self.arity
+        }
+
+        fn max_arity(&self) -> Option<usize> {
+            self.max_arity
+        }
+
+        fn call(
+            &self,
+            interpreter: &mut Interpreter,
+            arguments: Vec<Value>,
+            token: Token,
+        ) -> ExecuteInterpreterResult {
+            (self.function)(interpreter, arguments, &token).map(Some)
+        }
+
+        fn as_str(&self) -> String {
+            format!("<native fn {}>", self.name)
+        }
+    }
 
     #[derive(Debug, PartialEq)]
     pub struct ClockFunction {}
@@ -63,19 +163,14 @@ pub mod native {
             0
         }
 
+        /// Seconds since the Unix epoch, with a fractional part, from the interpreter's clock.
         fn call(
             &self,
-            _: &mut Interpreter,
+            interpreter: &mut Interpreter,
             _: Vec<Value>,
-            token: Token,
+            _: Token,
         ) -> ExecuteInterpreterResult {
-            match SystemTime::now().duration_since(UNIX_EPOCH) {
-                Ok(duration) => Ok(Some(Value::Number(duration.as_secs() as f64))),
-                Err(error) => Err(InterpreterError {
-                    token: Some(token),
-                    message: format!("SystemTime error: {}", error),
-                }),
-            }
+            Ok(Some(Value::Number(interpreter.clock.now().as_secs_f64())))
         }
 
         fn as_str(&self) -> String {
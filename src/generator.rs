@@ -0,0 +1,457 @@
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+
+use crate::{
+    Callable, Catch, Environment, ErrorKind, ExecuteInterpreterResult, Expression, Interpreter,
+    InterpreterError, LoxIterator, Statement, Token, Value,
+};
+
+/// The suspended body of a `fun*` call.
+///
+/// `Interpreter::execute_block` runs to completion on the Rust stack, so a generator keeps
+/// its own stack of frames for the statements that contain a `yield`. Statements without
+/// one are handed to the interpreter whole.
+#[derive(Debug)]
+pub struct Generator {
+    pub name: String,
+    frames: Vec<Frame>,
+}
+
+#[derive(Debug)]
+enum Frame {
+    Block {
+        statements: Vec<Statement>,
+        index: usize,
+        environment: Environment,
+    },
+    While {
+        condition: Expression,
+        body: Statement,
+        environment: Environment,
+    },
+    ForIn {
+        name: Token,
+        iterator: LoxIterator,
+        body: Statement,
+        environment: Environment,
+    },
+    /// A `try` statement, below the frame running the block it has reached.
+    Try {
+        catch: Option<Catch>,
+        finally: Option<Vec<Statement>>,
+        environment: Environment,
+        stage: TryStage,
+    },
+}
+
+#[derive(Debug)]
+enum TryStage {
+    Body,
+    Catch,
+    /// Running the `finally` block, with how the statement is left once it's done.
+    Finally(Option<Unwind>),
+}
+
+/// How the body is left before reaching the end of a block.
+#[derive(Debug)]
+enum Unwind {
+    Return,
+    Error(InterpreterError),
+}
+
+enum Step {
+    Continue,
+    Yield(Value),
+    Return,
+}
+
+impl Generator {
+    pub fn new(name: String, body: Vec<Statement>, environment: Environment) -> Self {
+        Generator {
+            name,
+            frames: vec![Frame::Block {
+                statements: body,
+                index: 0,
+                environment,
+            }],
+        }
+    }
+
+    /// Runs until the next `yield`, returning its value, or `None` once the body has finished.
+    pub fn resume(
+        &mut self,
+        interpreter: &mut Interpreter,
+    ) -> Result<Option<Value>, InterpreterError> {
+        match self.run(interpreter) {
+            Ok(Some(value)) => Ok(Some(value)),
+            result => {
+                self.frames.clear();
+                result
+            }
+        }
+    }
+
+    fn run(&mut self, interpreter: &mut Interpreter) -> Result<Option<Value>, InterpreterError> {
+        while !self.frames.is_empty() {
+            let unwind = match self.advance(interpreter) {
+                Ok(Step::Continue) => continue,
+                Ok(Step::Yield(value)) => return Ok(Some(value)),
+                Ok(Step::Return) => Unwind::Return,
+                Err(error) => Unwind::Error(error),
+            };
+
+            match self.unwind(unwind) {
+                None => {}
+                Some(Unwind::Return) => return Ok(None),
+                Some(Unwind::Error(error)) => return Err(error),
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Runs the innermost frame by one statement or iteration.
+    fn advance(&mut self, interpreter: &mut Interpreter) -> Result<Step, InterpreterError> {
+        let Some(frame) = self.frames.last_mut() else {
+            return Ok(Step::Continue);
+        };
+
+        match frame {
+            Frame::Block {
+                statements,
+                index,
+                environment,
+            } => {
+                let Some(statement) = statements.get(*index).cloned() else {
+                    self.frames.pop();
+                    return Ok(Step::Continue);
+                };
+
+                *index += 1;
+                let environment = environment.clone();
+
+                self.step(interpreter, statement, environment)
+            }
+            Frame::While {
+                condition,
+                body,
+                environment,
+            } => {
+                let result = interpreter.evaluate_in(condition.clone(), environment.clone())?;
+
+                if interpreter.is_truthy(result) {
+                    let frame = Frame::Block {
+                        statements: vec![body.clone()],
+                        index: 0,
+                        environment: environment.clone(),
+                    };
+                    self.frames.push(frame);
+                } else {
+                    self.frames.pop();
+                }
+
+                Ok(Step::Continue)
+            }
+            Frame::ForIn {
+                name,
+                iterator,
+                body,
+                environment,
+            } => {
+                match iterator.next(interpreter, name)? {
+                    Some(value) => {
+                        let mut environment = environment.enclose();
+                        environment.define(name.lexeme.clone(), value);
+
+                        let frame = Frame::Block {
+                            statements: vec![body.clone()],
+                            index: 0,
+                            environment,
+                        };
+                        self.frames.push(frame);
+                    }
+                    None => {
+                        self.frames.pop();
+                    }
+                }
+
+                Ok(Step::Continue)
+            }
+            // The body or catch block finished normally, or the finally block did.
+            Frame::Try {
+                finally,
+                environment,
+                stage,
+                ..
+            } => match stage {
+                TryStage::Body | TryStage::Catch => {
+                    match finally.clone() {
+                        Some(statements) => {
+                            *stage = TryStage::Finally(None);
+                            let frame = Frame::Block {
+                                statements,
+                                index: 0,
+                                environment: environment.enclose(),
+                            };
+                            self.frames.push(frame);
+                        }
+                        None => {
+                            self.frames.pop();
+                        }
+                    }
+
+                    Ok(Step::Continue)
+                }
+                TryStage::Finally(pending) => {
+                    let pending = pending.take();
+                    self.frames.pop();
+
+                    match pending {
+                        None => Ok(Step::Continue),
+                        Some(Unwind::Return) => Ok(Step::Return),
+                        Some(Unwind::Error(error)) => Err(error),
+                    }
+                }
+            },
+        }
+    }
+
+    /// Pops frames up to the innermost `try` statement that handles `unwind`, continuing in
+    /// its catch or finally block, like `Interpreter::execute` does for `Statement::Try`.
+    /// Returns `unwind` if no frame handles it.
+    fn unwind(&mut self, unwind: Unwind) -> Option<Unwind> {
+        while let Some(frame) = self.frames.pop() {
+            let Frame::Try {
+                catch,
+                finally,
+                environment,
+                stage,
+            } = frame
+            else {
+                continue;
+            };
+
+            // Errors other than `exit` raised by the body go to the catch block.
+            let caught = match (&stage, &unwind) {
+                (TryStage::Body, Unwind::Error(error)) => !matches!(error.kind, ErrorKind::Exit(_)),
+                _ => false,
+            };
+
+            if let (true, Some(Catch { name, body })) = (caught, &catch) {
+                let Unwind::Error(error) = unwind else {
+                    unreachable!();
+                };
+
+                let mut catch_environment = environment.enclose();
+                catch_environment.define(name.lexeme.clone(), error.into_value());
+                let body = body.clone();
+
+                self.frames.push(Frame::Try {
+                    catch,
+                    finally,
+                    environment,
+                    stage: TryStage::Catch,
+                });
+                self.frames.push(Frame::Block {
+                    statements: body,
+                    index: 0,
+                    environment: catch_environment,
+                });
+
+                return None;
+            }
+
+            // A `finally` block that returns or raises itself replaces the pending outcome.
+            if let (TryStage::Body | TryStage::Catch, Some(statements)) = (&stage, &finally) {
+                let frame = Frame::Block {
+                    statements: statements.clone(),
+                    index: 0,
+                    environment: environment.enclose(),
+                };
+
+                self.frames.push(Frame::Try {
+                    catch,
+                    finally,
+                    environment,
+                    stage: TryStage::Finally(Some(unwind)),
+                });
+                self.frames.push(frame);
+
+                return None;
+            }
+        }
+
+        Some(unwind)
+    }
+
+    fn step(
+        &mut self,
+        interpreter: &mut Interpreter,
+        statement: Statement,
+        environment: Environment,
+    ) -> Result<Step, InterpreterError> {
+        if !statement.contains_yield() {
+            return match interpreter.execute_block(vec![statement], environment)? {
+                Some(_) => Ok(Step::Return),
+                None => Ok(Step::Continue),
+            };
+        }
+
+        match statement {
+            Statement::Yield { value, .. } => {
+                let value = match value {
+                    Some(expression) => interpreter.evaluate_in(expression, environment)?,
+                    None => Value::Nil,
+                };
+
+                Ok(Step::Yield(value))
+            }
+            Statement::Block(statements) => {
+                self.frames.push(Frame::Block {
+                    statements,
+                    index: 0,
+                    environment: environment.enclose(),
+                });
+
+                Ok(Step::Continue)
+            }
+            Statement::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                let result = interpreter.evaluate_in(condition, environment.clone())?;
+
+                let branch = if interpreter.is_truthy(result) {
+                    Some(*then_branch)
+                } else {
+                    else_branch.map(|branch| *branch)
+                };
+
+                if let Some(branch) = branch {
+                    self.frames.push(Frame::Block {
+                        statements: vec![branch],
+                        index: 0,
+                        environment,
+                    });
+                }
+
+                Ok(Step::Continue)
+            }
+            Statement::While { condition, body } => {
+                self.frames.push(Frame::While {
+                    condition,
+                    body: *body,
+                    environment,
+                });
+
+                Ok(Step::Continue)
+            }
+            Statement::ForIn {
+                name,
+                iterable,
+                body,
+            } => {
+                let iterable = interpreter.evaluate_in(iterable, environment.clone())?;
+                let iterator = LoxIterator::new(iterable, interpreter, &name)?;
+
+                self.frames.push(Frame::ForIn {
+                    name,
+                    iterator,
+                    body: *body,
+                    environment,
+                });
+
+                Ok(Step::Continue)
+            }
+            Statement::Match {
+                keyword,
+                subject,
+                arms,
+            } => {
+                let value = interpreter.evaluate_in(subject, environment.clone())?;
+
+                let previous = std::mem::replace(&mut interpreter.environment, environment);
+                let selected = interpreter.select_arm(value, arms, &keyword);
+                interpreter.environment = previous;
+
+                let (body, environment) = selected?;
+                self.frames.push(Frame::Block {
+                    statements: vec![body],
+                    index: 0,
+                    environment,
+                });
+
+                Ok(Step::Continue)
+            }
+            Statement::Try {
+                body,
+                catch,
+                finally,
+            } => {
+                self.frames.push(Frame::Try {
+                    catch,
+                    finally,
+                    environment: environment.clone(),
+                    stage: TryStage::Body,
+                });
+                self.frames.push(Frame::Block {
+                    statements: body,
+                    index: 0,
+                    environment: environment.enclose(),
+                });
+
+                Ok(Step::Continue)
+            }
+            _ => panic!("unreachable"),
+        }
+    }
+}
+
+/// Resumes `generator`, reporting a call from inside its own body as a runtime error.
+pub fn resume_generator(
+    generator: &Rc<RefCell<Generator>>,
+    interpreter: &mut Interpreter,
+    token: &Token,
+) -> Result<Option<Value>, InterpreterError> {
+    let Ok(mut generator) = generator.try_borrow_mut() else {
+        return Err(InterpreterError {
+            token: Some(token.clone()),
+            message: "Generator is already running.".into(),
+            kind: ErrorKind::Runtime,
+        });
+    };
+
+    generator.resume(interpreter)
+}
+
+/// The `next` method of a generator object, returning a `{done, value}` map like the
+/// iterators accepted by `for (var x in ...)`.
+#[derive(Debug)]
+pub struct GeneratorNext {
+    pub generator: Rc<RefCell<Generator>>,
+}
+
+impl Callable for GeneratorNext {
+    fn arity(&self) -> usize {
+        0
+    }
+
+    fn call(
+        &self,
+        interpreter: &mut Interpreter,
+        _: Vec<Value>,
+        token: Token,
+    ) -> ExecuteInterpreterResult {
+        let value = resume_generator(&self.generator, interpreter, &token)?;
+
+        let mut result: BTreeMap<String, Value> = BTreeMap::new();
+        result.insert("done".into(), Value::Boolean(value.is_none()));
+        result.insert("value".into(), value.unwrap_or(Value::Nil));
+
+        Ok(Some(Value::Map(Rc::new(RefCell::new(result)))))
+    }
+
+    fn as_str(&self) -> String {
+        "<native fn next>".into()
+    }
+}
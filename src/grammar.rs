@@ -7,6 +7,8 @@ pub enum TokenType {
     RightParen,
     LeftBrace,
     RightBrace,
+    LeftBracket,
+    RightBracket,
 
     Comma,
     Dot,
@@ -15,16 +17,34 @@ pub enum TokenType {
     Semicolon,
     Slash,
     Star,
+    Percent,
+    Ampersand,
+    Pipe,
+    Caret,
+    Colon,
 
     // One or two character tokens.
     Bang,
     BangEqual,
     Equal,
     EqualEqual,
+    FatArrow,
     Greater,
     GreaterEqual,
+    GreaterGreater,
     Less,
     LessEqual,
+    LessLess,
+    StarStar,
+    PlusEqual,
+    MinusEqual,
+    StarEqual,
+    SlashEqual,
+    PercentEqual,
+    Question,
+    QuestionQuestion,
+    DotDot,
+    DotDotDot,
 
     // Literals.
     Identifier,
@@ -33,21 +53,31 @@ pub enum TokenType {
 
     // Keywords.
     And,
+    Assert,
+    Catch,
     Class,
+    Const,
     Else,
+    Enum,
     False,
+    Finally,
     Fun,
     For,
     If,
+    Import,
+    Match,
     Nil,
     Or,
     Print,
     Return,
     Super,
     This,
+    Throw,
     True,
+    Try,
     Var,
     While,
+    Yield,
 
     // Other.
     Eof,
@@ -60,6 +90,8 @@ impl fmt::Display for TokenType {
             TokenType::RightParen => write!(f, "RIGHT_PAREN"),
             TokenType::LeftBrace => write!(f, "LEFT_BRACE"),
             TokenType::RightBrace => write!(f, "RIGHT_BRACE"),
+            TokenType::LeftBracket => write!(f, "LEFT_BRACKET"),
+            TokenType::RightBracket => write!(f, "RIGHT_BRACKET"),
             TokenType::Comma => write!(f, "COMMA"),
             TokenType::Dot => write!(f, "DOT"),
             TokenType::Minus => write!(f, "MINUS"),
@@ -67,33 +99,61 @@ impl fmt::Display for TokenType {
             TokenType::Semicolon => write!(f, "SEMICOLON"),
             TokenType::Slash => write!(f, "SLASH"),
             TokenType::Star => write!(f, "STAR"),
+            TokenType::Percent => write!(f, "PERCENT"),
+            TokenType::Ampersand => write!(f, "AMPERSAND"),
+            TokenType::Pipe => write!(f, "PIPE"),
+            TokenType::Caret => write!(f, "CARET"),
+            TokenType::Colon => write!(f, "COLON"),
             TokenType::Bang => write!(f, "BANG"),
             TokenType::BangEqual => write!(f, "BANG_EQUAL"),
             TokenType::Equal => write!(f, "EQUAL"),
             TokenType::EqualEqual => write!(f, "EQUAL_EQUAL"),
+            TokenType::FatArrow => write!(f, "FAT_ARROW"),
             TokenType::Greater => write!(f, "GREATER"),
             TokenType::GreaterEqual => write!(f, "GREATER_EQUAL"),
+            TokenType::GreaterGreater => write!(f, "GREATER_GREATER"),
             TokenType::Less => write!(f, "LESS"),
             TokenType::LessEqual => write!(f, "LESS_EQUAL"),
+            TokenType::LessLess => write!(f, "LESS_LESS"),
+            TokenType::StarStar => write!(f, "STAR_STAR"),
+            TokenType::PlusEqual => write!(f, "PLUS_EQUAL"),
+            TokenType::MinusEqual => write!(f, "MINUS_EQUAL"),
+            TokenType::StarEqual => write!(f, "STAR_EQUAL"),
+            TokenType::SlashEqual => write!(f, "SLASH_EQUAL"),
+            TokenType::PercentEqual => write!(f, "PERCENT_EQUAL"),
+            TokenType::Question => write!(f, "QUESTION"),
+            TokenType::QuestionQuestion => write!(f, "QUESTION_QUESTION"),
+            TokenType::DotDot => write!(f, "DOT_DOT"),
+            TokenType::DotDotDot => write!(f, "DOT_DOT_DOT"),
             TokenType::Identifier => write!(f, "IDENTIFIER"),
             TokenType::String => write!(f, "STRING"),
             TokenType::Number => write!(f, "NUMBER"),
             TokenType::And => write!(f, "AND"),
+            TokenType::Assert => write!(f, "ASSERT"),
+            TokenType::Catch => write!(f, "CATCH"),
             TokenType::Class => write!(f, "CLASS"),
+            TokenType::Const => write!(f, "CONST"),
             TokenType::Else => write!(f, "ELSE"),
+            TokenType::Enum => write!(f, "ENUM"),
             TokenType::False => write!(f, "FALSE"),
+            TokenType::Finally => write!(f, "FINALLY"),
             TokenType::Fun => write!(f, "FUN"),
             TokenType::For => write!(f, "FOR"),
             TokenType::If => write!(f, "IF"),
+            TokenType::Import => write!(f, "IMPORT"),
+            TokenType::Match => write!(f, "MATCH"),
             TokenType::Nil => write!(f, "NIL"),
             TokenType::Or => write!(f, "OR"),
             TokenType::Print => write!(f, "PRINT"),
             TokenType::Return => write!(f, "RETURN"),
             TokenType::Super => write!(f, "SUPER"),
             TokenType::This => write!(f, "THIS"),
+            TokenType::Throw => write!(f, "THROW"),
             TokenType::True => write!(f, "TRUE"),
+            TokenType::Try => write!(f, "TRY"),
             TokenType::Var => write!(f, "VAR"),
             TokenType::While => write!(f, "WHILE"),
+            TokenType::Yield => write!(f, "YIELD"),
             TokenType::Eof => write!(f, "EOF"),
         }
     }
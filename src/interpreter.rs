@@ -1,12 +1,49 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+    rc::Rc,
+};
 
-use crate::{native, Environment, Expression, LoxFunction, Statement, Token, TokenType, Value};
+use crate::{
+    native, stdlib, Callable, Capabilities, Catch, Clock, Environment, Expression, GeneratorNext,
+    InstanceText, Literal, LoxClass, LoxEnum, LoxError, LoxFunction, LoxIterator, LoxModule,
+    LoxTrait, LoxVariant, MatchArm, Method, MethodKind, Random, Range, Regex, Statement,
+    SystemClock, Token, TokenType, Value, VariantConstructor,
+};
 
 #[derive(Debug, thiserror::Error)]
 #[error("{message}")]
 pub struct InterpreterError {
     pub token: Option<Token>,
     pub message: String,
+    pub kind: ErrorKind,
+}
+
+#[derive(Debug)]
+pub enum ErrorKind {
+    /// An error raised by the interpreter itself, caught as a `LoxError` value.
+    Runtime,
+    /// A value raised by a `throw` statement.
+    Thrown(Value),
+    /// A failed `assert` statement, caught as a `LoxError` value like a runtime error.
+    Assertion,
+    /// A call to `exit`, which unwinds the whole script without being caught.
+    Exit(i32),
+}
+
+impl InterpreterError {
+    /// The value bound to the variable of a `catch` clause.
+    pub fn into_value(self) -> Value {
+        match self.kind {
+            ErrorKind::Thrown(value) => value,
+            kind => Value::Error(Rc::new(LoxError {
+                message: self.message,
+                line: self.token.map(|token| token.line),
+                assertion: matches!(kind, ErrorKind::Assertion),
+            })),
+        }
+    }
 }
 
 pub type ExecuteInterpreterResult = Result<Option<Value>, InterpreterError>;
@@ -14,23 +51,72 @@ pub type EvaluateInterpreterResult = Result<Value, InterpreterError>;
 
 #[derive(Debug)]
 pub struct Interpreter {
+    /// Native functions, visible from the script and from every imported module.
+    pub builtins: Environment,
     pub globals: Environment,
     pub environment: Environment,
+    /// The file being executed, used to resolve relative import paths.
+    pub current_file: Option<PathBuf>,
+    pub modules: HashMap<PathBuf, Rc<LoxModule>>,
+    /// Modules whose execution is in progress, innermost last.
+    pub importing: Vec<PathBuf>,
+    /// The time source of the clock natives.
+    pub clock: Box<dyn Clock>,
+    /// What the I/O natives may access.
+    pub capabilities: Capabilities,
+    /// Compiled regular expressions, by pattern.
+    pub regexes: HashMap<String, Rc<Regex>>,
+    /// The generator behind the random natives.
+    pub random: Random,
+    /// Whether `print` statements discard their output.
+    pub quiet: bool,
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Interpreter {
     pub fn new() -> Self {
-        let mut environment = Environment::new();
+        let mut builtins = Environment::new();
 
-        environment.define(
+        builtins.define(
             "clock".into(),
             Value::Function(Rc::new(RefCell::new(native::ClockFunction {}))),
         );
+        stdlib::define(&mut builtins);
 
-        Interpreter {
+        let environment = builtins.enclose();
+
+        let mut interpreter = Interpreter {
+            builtins,
             globals: environment.clone(),
             environment,
-        }
+            current_file: None,
+            modules: HashMap::new(),
+            importing: Vec::new(),
+            clock: Box::new(SystemClock::new()),
+            capabilities: Capabilities::default(),
+            regexes: HashMap::new(),
+            random: Random::from_time(),
+            quiet: false,
+        };
+        interpreter.set_arguments(Vec::new());
+
+        interpreter
+    }
+
+    /// Makes `arguments` visible to scripts and modules as the `ARGS` list.
+    pub fn set_arguments(&mut self, arguments: Vec<String>) {
+        let arguments = arguments
+            .into_iter()
+            .map(|argument| Value::String(Rc::new(argument)))
+            .collect();
+
+        self.builtins
+            .define_constant("ARGS".into(), Value::List(Rc::new(RefCell::new(arguments))));
     }
 
     pub fn interpret(&mut self, statements: Vec<Statement>) -> ExecuteInterpreterResult {
@@ -51,19 +137,25 @@ impl Interpreter {
             Statement::Function {
                 name,
                 parameters,
+                rest,
                 body,
+                generator,
             } => {
                 let function = LoxFunction {
-                    name,
+                    name: name.clone(),
                     parameters,
+                    rest,
                     body,
                     closure: self.environment.clone(),
+                    generator,
+                    initializer: false,
                 };
 
-                self.environment.define(
-                    function.get_name().into(),
+                self.environment.declare(
+                    &name,
                     Value::Function(Rc::new(RefCell::new(function))),
-                );
+                    false,
+                )?;
 
                 Ok(None)
             }
@@ -82,21 +174,30 @@ impl Interpreter {
                     Ok(None)
                 }
             }
-            Statement::Print(expression) => {
-                match self.evaluate(expression)? {
-                    Value::Number(value) => println!("{value}"),
-                    value => println!("{value}"),
+            Statement::Print(expressions) => {
+                let mut values: Vec<String> = Vec::new();
+                for expression in expressions {
+                    let value = self.evaluate(expression)?;
+                    values.push(self.stringify(&value)?);
+                }
+
+                if !self.quiet {
+                    println!("{}", values.join(" "));
                 }
 
                 Ok(None)
             }
-            Statement::Variable { name, initializer } => {
+            Statement::Variable {
+                name,
+                initializer,
+                constant,
+            } => {
                 let mut value = Value::Nil;
                 if let Some(expression) = initializer {
                     value = self.evaluate(expression)?;
                 }
 
-                self.environment.define(name.lexeme, value);
+                self.environment.declare(&name, value, constant)?;
 
                 Ok(None)
             }
@@ -122,12 +223,330 @@ impl Interpreter {
 
                 Ok(None)
             }
+            Statement::ForIn {
+                name,
+                iterable,
+                body,
+            } => {
+                let iterable_value = self.evaluate(iterable)?;
+                let mut iterator = LoxIterator::new(iterable_value, self, &name)?;
+
+                while let Some(value) = iterator.next(self, &name)? {
+                    // Each iteration gets a fresh binding so closures capture its own value.
+                    let mut environment = self.environment.enclose();
+                    environment.define(name.lexeme.clone(), value);
+
+                    if let Some(returned) = self.execute_block(vec![*body.clone()], environment)? {
+                        return Ok(Some(returned));
+                    }
+                }
+
+                Ok(None)
+            }
             Statement::Block(statements) => {
                 Ok(self.execute_block(statements, self.environment.enclose())?)
             }
+            Statement::Throw { keyword, value } => {
+                let value = self.evaluate(value)?;
+
+                Err(InterpreterError {
+                    token: Some(keyword),
+                    message: self.stringify(&value)?,
+                    kind: ErrorKind::Thrown(value),
+                })
+            }
+            Statement::Assert {
+                keyword,
+                condition,
+                message,
+            } => {
+                let result = self.evaluate(condition)?;
+
+                if self.is_truthy(result) {
+                    return Ok(None);
+                }
+
+                let message = match message {
+                    Some(message) => {
+                        let message = self.evaluate(message)?;
+                        format!("Assertion failed: {}", self.stringify(&message)?)
+                    }
+                    None => "Assertion failed.".into(),
+                };
+
+                Err(InterpreterError {
+                    token: Some(keyword),
+                    message,
+                    kind: ErrorKind::Assertion,
+                })
+            }
+            Statement::Try {
+                body,
+                catch,
+                finally,
+            } => {
+                let mut result = self.execute_block(body, self.environment.enclose());
+
+                if let Some(Catch { name, body }) = catch {
+                    result = match result {
+                        Err(error) if !matches!(error.kind, ErrorKind::Exit(_)) => {
+                            let mut environment = self.environment.enclose();
+                            environment.define(name.lexeme, error.into_value());
+
+                            self.execute_block(body, environment)
+                        }
+                        result => result,
+                    };
+                }
+
+                // A `finally` block runs however the try and catch blocks were left, and
+                // only replaces their outcome when it returns or raises itself.
+                if let Some(statements) = finally {
+                    if let Some(returned) =
+                        self.execute_block(statements, self.environment.enclose())?
+                    {
+                        return Ok(Some(returned));
+                    }
+                }
+
+                result
+            }
+            Statement::Import {
+                keyword,
+                path,
+                alias,
+                names,
+            } => {
+                let Some(Literal::String(path)) = path.literal else {
+                    panic!("unreachable");
+                };
+
+                let module = self.import_module(&keyword, &path)?;
+
+                for name in names {
+                    let Some(value) = module.environment.get_local(&name.lexeme) else {
+                        return Err(InterpreterError {
+                            message: format!("Module '{path}' has no member '{}'.", name.lexeme),
+                            token: Some(name),
+                            kind: ErrorKind::Runtime,
+                        });
+                    };
+
+                    self.environment.declare(&name, value, false)?;
+                }
+
+                if let Some(alias) = alias {
+                    self.environment
+                        .declare(&alias, Value::Module(module), false)?;
+                }
+
+                Ok(None)
+            }
+            Statement::Match {
+                keyword,
+                subject,
+                arms,
+            } => {
+                let value = self.evaluate(subject)?;
+                let (body, environment) = self.select_arm(value, arms, &keyword)?;
+
+                self.execute_block(vec![body], environment)
+            }
+            Statement::Enum { name, variants } => {
+                let enumeration = LoxEnum {
+                    name: name.lexeme.clone(),
+                    variants: variants
+                        .into_iter()
+                        .map(|variant| {
+                            let fields = variant.fields.into_iter().map(|field| field.lexeme);
+                            (variant.name.lexeme, fields.collect())
+                        })
+                        .collect(),
+                };
+
+                self.environment
+                    .declare(&name, Value::Enum(Rc::new(enumeration)), false)?;
+
+                Ok(None)
+            }
+            Statement::Class {
+                name,
+                superclass,
+                traits,
+                methods,
+            } => {
+                let superclass = match superclass {
+                    Some(expression) => match self.evaluate(expression)? {
+                        Value::Class(class) => Some(class),
+                        _ => {
+                            return Err(InterpreterError {
+                                token: Some(name),
+                                message: "Superclass must be a class.".into(),
+                                kind: ErrorKind::Runtime,
+                            })
+                        }
+                    },
+                    None => None,
+                };
+
+                let mut trait_values: Vec<Rc<LoxTrait>> = Vec::new();
+                for expression in traits {
+                    match self.evaluate(expression)? {
+                        Value::Trait(r#trait) => trait_values.push(r#trait),
+                        _ => {
+                            return Err(InterpreterError {
+                                token: Some(name),
+                                message: "Can only mix traits into a class.".into(),
+                                kind: ErrorKind::Runtime,
+                            })
+                        }
+                    }
+                }
+
+                // Methods of a subclass see its superclass as `super`.
+                let mut closure = self.environment.clone();
+                if let Some(superclass) = &superclass {
+                    closure = closure.enclose();
+                    closure.define("super".into(), Value::Class(superclass.clone()));
+                }
+
+                let mut class = LoxClass::new(name.lexeme.clone(), superclass);
+
+                for method in methods {
+                    let (kind, method) = self.method(method, &closure);
+
+                    class
+                        .table_mut(kind)
+                        .insert(method.get_name().into(), Rc::new(method));
+                }
+
+                // The class's own methods take precedence over those of its traits, but two
+                // traits can't both provide one it doesn't define.
+                let mut provided: HashMap<(MethodKind, String), &str> = HashMap::new();
+                for r#trait in &trait_values {
+                    for (kind, method) in &r#trait.methods {
+                        let key = (*kind, method.get_name().to_string());
+
+                        if let Some(other) = provided.get(&key) {
+                            return Err(InterpreterError {
+                                message: format!(
+                                    "Traits '{other}' and '{}' both define '{}'.",
+                                    r#trait.name,
+                                    method.get_name()
+                                ),
+                                token: Some(name),
+                                kind: ErrorKind::Runtime,
+                            });
+                        }
+
+                        if class.table_mut(*kind).contains_key(&key.1) {
+                            continue;
+                        }
+
+                        class.table_mut(*kind).insert(key.1.clone(), method.clone());
+                        provided.insert(key, &r#trait.name);
+                    }
+                }
+
+                class.traits = trait_values;
+
+                self.environment
+                    .declare(&name, Value::Class(Rc::new(class)), false)?;
+
+                Ok(None)
+            }
+            Statement::Trait { name, methods } => {
+                let closure = self.environment.clone();
+
+                let methods = methods
+                    .into_iter()
+                    .map(|method| {
+                        let (kind, method) = self.method(method, &closure);
+                        (kind, Rc::new(method))
+                    })
+                    .collect();
+
+                let r#trait = LoxTrait {
+                    name: name.lexeme.clone(),
+                    methods,
+                };
+
+                self.environment
+                    .declare(&name, Value::Trait(Rc::new(r#trait)), false)?;
+
+                Ok(None)
+            }
+            Statement::Yield { keyword, .. } => Err(InterpreterError {
+                token: Some(keyword),
+                message: "Can't yield outside a generator.".into(),
+                kind: ErrorKind::Runtime,
+            }),
         }
     }
 
+    /// The function for a method of a class or trait declaration, closing over `closure`.
+    fn method(&self, method: Method, closure: &Environment) -> (MethodKind, LoxFunction) {
+        let Statement::Function {
+            name,
+            parameters,
+            rest,
+            body,
+            generator,
+        } = method.function
+        else {
+            panic!("unreachable");
+        };
+
+        let function = LoxFunction {
+            initializer: method.kind == MethodKind::Method && name.lexeme == "init",
+            name,
+            parameters,
+            rest,
+            body,
+            closure: closure.clone(),
+            generator,
+        };
+
+        (method.kind, function)
+    }
+
+    /// Finds the first arm whose pattern and guard accept `value`, returning its body and
+    /// the environment holding the pattern's bindings.
+    pub fn select_arm(
+        &mut self,
+        value: Value,
+        arms: Vec<MatchArm>,
+        keyword: &Token,
+    ) -> Result<(Statement, Environment), InterpreterError> {
+        for arm in arms {
+            let mut bindings: Vec<(String, Value)> = Vec::new();
+            if !arm.pattern.matches(&value, self, &mut bindings)? {
+                continue;
+            }
+
+            let mut environment = self.environment.enclose();
+            for (name, value) in bindings {
+                environment.define(name, value);
+            }
+
+            if let Some(guard) = arm.guard {
+                let result = self.evaluate_in(guard, environment.clone())?;
+
+                if !self.is_truthy(result) {
+                    continue;
+                }
+            }
+
+            return Ok((arm.body, environment));
+        }
+
+        Err(InterpreterError {
+            token: Some(keyword.clone()),
+            message: format!("No match arm for value {value}."),
+            kind: ErrorKind::Runtime,
+        })
+    }
+
     pub fn execute_block(
         &mut self,
         statements: Vec<Statement>,
@@ -161,6 +580,14 @@ impl Interpreter {
             Expression::Unary { operator, right } => {
                 let right_child = self.evaluate(*right)?;
 
+                if operator.token_type == TokenType::Minus {
+                    if let Some(result) =
+                        self.call_special(&right_child, "__neg__", Vec::new(), &operator)?
+                    {
+                        return Ok(result);
+                    }
+                }
+
                 match operator.token_type {
                     TokenType::Bang => Ok(Value::Boolean(!self.is_truthy(right_child))),
                     TokenType::Minus => Ok(Value::Number(
@@ -177,80 +604,15 @@ impl Interpreter {
                 let left_child = self.evaluate(*left)?;
                 let right_child = self.evaluate(*right)?;
 
-                match operator.token_type {
-                    TokenType::Slash => {
-                        let (x, y) =
-                            self.check_number_operands(&operator, &left_child, &right_child)?;
-
-                        return Ok(Value::Number(x / y));
-                    }
-                    TokenType::Star => {
-                        let (x, y) =
-                            self.check_number_operands(&operator, &left_child, &right_child)?;
-
-                        return Ok(Value::Number(x * y));
-                    }
-                    TokenType::Minus => {
-                        let (x, y) =
-                            self.check_number_operands(&operator, &left_child, &right_child)?;
-
-                        return Ok(Value::Number(x - y));
-                    }
-                    TokenType::Plus => {
-                        if let (Value::Number(a), Value::Number(b)) = (&left_child, &right_child) {
-                            return Ok(Value::Number(*a + *b));
-                        }
-
-                        if let (Value::String(a), Value::String(b)) = (&left_child, &right_child) {
-                            let mut output: String = a.as_str().into();
-                            output.push_str(b);
-
-                            return Ok(Value::String(Rc::new(output)));
-                        }
-
-                        Err(InterpreterError {
-                            token: Some(operator.clone()),
-                            message: "Operands must be two numbers or two strings.".into(),
-                        })
-                    }
-                    TokenType::Greater => {
-                        let (x, y) =
-                            self.check_number_operands(&operator, &left_child, &right_child)?;
-
-                        return Ok(Value::Boolean(x > y));
-                    }
-                    TokenType::GreaterEqual => {
-                        let (x, y) =
-                            self.check_number_operands(&operator, &left_child, &right_child)?;
-
-                        return Ok(Value::Boolean(x >= y));
-                    }
-                    TokenType::Less => {
-                        let (x, y) =
-                            self.check_number_operands(&operator, &left_child, &right_child)?;
-
-                        return Ok(Value::Boolean(x < y));
-                    }
-                    TokenType::LessEqual => {
-                        let (x, y) =
-                            self.check_number_operands(&operator, &left_child, &right_child)?;
-
-                        return Ok(Value::Boolean(x <= y));
-                    }
-                    TokenType::BangEqual => Ok(Value::Boolean(left_child != right_child)),
-                    TokenType::EqualEqual => Ok(Value::Boolean(left_child == right_child)),
-                    _ => panic!("unreachable"),
-                }
-            }
-            Expression::Variable(name) => {
-                return self.environment.get(&name);
+                self.binary(&operator, left_child, right_child)
             }
+            Expression::Variable(name) => self.environment.get(&name),
             Expression::Assign { name, right } => {
                 let value = self.evaluate(*right)?;
 
                 self.environment.assign(&name, &value)?;
 
-                return Ok(value);
+                Ok(value)
             }
             Expression::Logical {
                 left,
@@ -275,6 +637,13 @@ impl Interpreter {
 
                         self.evaluate(*right)
                     }
+                    TokenType::QuestionQuestion => {
+                        if left_value != Value::Nil {
+                            return Ok(left_value);
+                        }
+
+                        self.evaluate(*right)
+                    }
                     _ => panic!("unreachable"),
                 }
             }
@@ -285,37 +654,757 @@ impl Interpreter {
             } => {
                 let callee_value = self.evaluate(*callee)?;
 
-                let mut arguments_values: Vec<Value> = Vec::new();
-                for argument in arguments {
-                    let argument_value = self.evaluate(argument)?;
-                    arguments_values.push(argument_value);
+                let arguments_values = self.evaluate_elements(arguments)?;
+
+                self.call_value(callee_value, arguments_values, parenthesis)
+            }
+            Expression::Conditional {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                let result = self.evaluate(*condition)?;
+
+                if self.is_truthy(result) {
+                    self.evaluate(*then_branch)
+                } else {
+                    self.evaluate(*else_branch)
                 }
+            }
+            Expression::Get { object, name } => {
+                let object_value = self.evaluate(*object)?;
 
-                if let Value::Function(callable) = callee_value {
-                    let arity = callable.borrow().arity();
-                    if arguments_values.len() != arity {
-                        return Err(InterpreterError {
-                            token: Some(parenthesis.clone()),
-                            message: format!(
-                                "Expected {arity} arguments but got {}.",
-                                arguments_values.len()
-                            ),
-                        });
+                self.get_property(object_value, &name)
+            }
+            Expression::Set {
+                object,
+                name,
+                right,
+            } => {
+                let object_value = self.evaluate(*object)?;
+                let value = self.evaluate(*right)?;
+
+                self.set_property(object_value, &name, value.clone())?;
+
+                Ok(value)
+            }
+            Expression::CompoundSet {
+                object,
+                name,
+                operator,
+                right,
+            } => {
+                let object_value = self.evaluate(*object)?;
+
+                let current = self.get_property(object_value.clone(), &name)?;
+                let right_value = self.evaluate(*right)?;
+                let value = self.binary(&operator, current, right_value)?;
+
+                self.set_property(object_value, &name, value.clone())?;
+
+                Ok(value)
+            }
+            Expression::List(elements) => {
+                let values = self.evaluate_elements(elements)?;
+
+                Ok(Value::List(Rc::new(RefCell::new(values))))
+            }
+            Expression::Map(entries) => {
+                let mut map: BTreeMap<String, Value> = BTreeMap::new();
+
+                for (key, expression) in entries {
+                    let value = self.evaluate(expression)?;
+                    map.insert(key, value);
+                }
+
+                Ok(Value::Map(Rc::new(RefCell::new(map))))
+            }
+            Expression::Spread { operator, .. } => Err(InterpreterError {
+                token: Some(operator),
+                message: "Can only spread inside a list or argument list.".into(),
+                kind: ErrorKind::Runtime,
+            }),
+            Expression::Index {
+                object,
+                bracket,
+                index,
+            } => {
+                let object_value = self.evaluate(*object)?;
+                let index_value = self.evaluate(*index)?;
+
+                self.get_index(object_value, index_value, &bracket)
+            }
+            Expression::SetIndex {
+                object,
+                bracket,
+                index,
+                right,
+            } => {
+                let object_value = self.evaluate(*object)?;
+                let index_value = self.evaluate(*index)?;
+                let value = self.evaluate(*right)?;
+
+                self.set_index(object_value, index_value, value.clone(), &bracket)?;
+
+                Ok(value)
+            }
+            Expression::CompoundSetIndex {
+                object,
+                bracket,
+                index,
+                operator,
+                right,
+            } => {
+                let object_value = self.evaluate(*object)?;
+                let index_value = self.evaluate(*index)?;
+
+                let current =
+                    self.get_index(object_value.clone(), index_value.clone(), &bracket)?;
+                let right_value = self.evaluate(*right)?;
+                let value = self.binary(&operator, current, right_value)?;
+
+                self.set_index(object_value, index_value, value.clone(), &bracket)?;
+
+                Ok(value)
+            }
+            Expression::This(keyword) => self.environment.get(&keyword),
+            Expression::Super { keyword, method } => {
+                let Value::Class(superclass) = self.environment.get(&keyword)? else {
+                    panic!("unreachable");
+                };
+
+                let this = Token {
+                    lexeme: "this".into(),
+                    ..keyword.clone()
+                };
+                let receiver = self.environment.get(&this)?;
+
+                // In a class method, `this` is the class and `super` reaches its statics.
+                let function = match receiver {
+                    Value::Class(_) => superclass.find_static(&method.lexeme),
+                    _ => {
+                        if let Some(getter) = superclass.find_getter(&method.lexeme) {
+                            let getter =
+                                Value::Function(Rc::new(RefCell::new(getter.bind(receiver))));
+                            return self.call_value(getter, Vec::new(), method);
+                        }
+
+                        superclass.find_method(&method.lexeme)
                     }
+                };
 
-                    let returned_value =
-                        callable
-                            .borrow()
-                            .call(self, arguments_values, parenthesis)?;
-                    return Ok(returned_value.unwrap_or(Value::Nil));
-                } else {
-                    Err(InterpreterError {
-                        token: Some(parenthesis.clone()),
-                        message: "Can only call functions and classes.".into(),
-                    })
+                match function {
+                    Some(function) => Ok(Value::Function(Rc::new(RefCell::new(
+                        function.bind(receiver),
+                    )))),
+                    None => Err(InterpreterError {
+                        message: format!("Undefined property '{}'.", method.lexeme),
+                        token: Some(method),
+                        kind: ErrorKind::Runtime,
+                    }),
+                }
+            }
+        }
+    }
+
+    /// Applies a binary operator to its evaluated operands.
+    fn binary(
+        &mut self,
+        operator: &Token,
+        left_child: Value,
+        right_child: Value,
+    ) -> EvaluateInterpreterResult {
+        if let Some(result) = self.binary_special(operator, &left_child, &right_child)? {
+            return Ok(result);
+        }
+
+        match operator.token_type {
+            TokenType::Slash => {
+                let (x, y) = self.check_number_operands(operator, &left_child, &right_child)?;
+
+                Ok(Value::Number(x / y))
+            }
+            TokenType::Star => {
+                let (x, y) = self.check_number_operands(operator, &left_child, &right_child)?;
+
+                Ok(Value::Number(x * y))
+            }
+            TokenType::Percent => {
+                let (x, y) = self.check_number_operands(operator, &left_child, &right_child)?;
+
+                Ok(Value::Number(x % y))
+            }
+            TokenType::StarStar => {
+                let (x, y) = self.check_number_operands(operator, &left_child, &right_child)?;
+
+                Ok(Value::Number(x.powf(y)))
+            }
+            TokenType::Ampersand => {
+                let (x, y) = self.check_integer_operands(operator, &left_child, &right_child)?;
+
+                Ok(Value::Number((x & y) as f64))
+            }
+            TokenType::Pipe => {
+                let (x, y) = self.check_integer_operands(operator, &left_child, &right_child)?;
+
+                Ok(Value::Number((x | y) as f64))
+            }
+            TokenType::Caret => {
+                let (x, y) = self.check_integer_operands(operator, &left_child, &right_child)?;
+
+                Ok(Value::Number((x ^ y) as f64))
+            }
+            TokenType::LessLess | TokenType::GreaterGreater => {
+                let (x, y) = self.check_integer_operands(operator, &left_child, &right_child)?;
+
+                let shifted = u32::try_from(y).ok().and_then(|amount| {
+                    if operator.token_type == TokenType::LessLess {
+                        x.checked_shl(amount)
+                    } else {
+                        x.checked_shr(amount)
+                    }
+                });
+
+                match shifted {
+                    Some(value) => Ok(Value::Number(value as f64)),
+                    None => Err(InterpreterError {
+                        token: Some(operator.clone()),
+                        message: "Shift amount must be between 0 and 63.".into(),
+                        kind: ErrorKind::Runtime,
+                    }),
+                }
+            }
+            TokenType::Minus => {
+                let (x, y) = self.check_number_operands(operator, &left_child, &right_child)?;
+
+                Ok(Value::Number(x - y))
+            }
+            TokenType::Plus => {
+                if let (Value::Number(a), Value::Number(b)) = (&left_child, &right_child) {
+                    return Ok(Value::Number(*a + *b));
+                }
+
+                if let (Value::String(a), Value::String(b)) = (&left_child, &right_child) {
+                    let mut output: String = a.as_str().into();
+                    output.push_str(b);
+
+                    return Ok(Value::String(Rc::new(output)));
+                }
+
+                Err(InterpreterError {
+                    token: Some(operator.clone()),
+                    message: "Operands must be two numbers or two strings.".into(),
+                    kind: ErrorKind::Runtime,
+                })
+            }
+            TokenType::Greater => {
+                let (x, y) = self.check_number_operands(operator, &left_child, &right_child)?;
+
+                Ok(Value::Boolean(x > y))
+            }
+            TokenType::GreaterEqual => {
+                let (x, y) = self.check_number_operands(operator, &left_child, &right_child)?;
+
+                Ok(Value::Boolean(x >= y))
+            }
+            TokenType::Less => {
+                let (x, y) = self.check_number_operands(operator, &left_child, &right_child)?;
+
+                Ok(Value::Boolean(x < y))
+            }
+            TokenType::LessEqual => {
+                let (x, y) = self.check_number_operands(operator, &left_child, &right_child)?;
+
+                Ok(Value::Boolean(x <= y))
+            }
+            TokenType::DotDot => {
+                let (start, end) =
+                    self.check_number_operands(operator, &left_child, &right_child)?;
+
+                if start.is_nan() || end.is_nan() {
+                    return Err(InterpreterError {
+                        token: Some(operator.clone()),
+                        message: "Range bounds can't be NaN.".into(),
+                        kind: ErrorKind::Runtime,
+                    });
+                }
+
+                Ok(Value::Range(Range {
+                    start,
+                    end,
+                    step: 1.0,
+                }))
+            }
+            TokenType::BangEqual => Ok(Value::Boolean(left_child != right_child)),
+            TokenType::EqualEqual => Ok(Value::Boolean(left_child == right_child)),
+            _ => panic!("unreachable"),
+        }
+    }
+
+    /// Dispatches a binary operator to a special method when the operand it belongs to is
+    /// an instance: `a + b` calls `a.__add__(b)`, `a != b` negates `a.__eq__(b)`, and the
+    /// comparisons are derived from `__lt__`, so `a > b` calls `b.__lt__(a)`.
+    fn binary_special(
+        &mut self,
+        operator: &Token,
+        left: &Value,
+        right: &Value,
+    ) -> Result<Option<Value>, InterpreterError> {
+        let (receiver, name, argument, negate) = match operator.token_type {
+            TokenType::Plus => (left, "__add__", right, false),
+            TokenType::Minus => (left, "__sub__", right, false),
+            TokenType::Star => (left, "__mul__", right, false),
+            TokenType::Slash => (left, "__div__", right, false),
+            TokenType::EqualEqual => (left, "__eq__", right, false),
+            TokenType::BangEqual => (left, "__eq__", right, true),
+            TokenType::Less => (left, "__lt__", right, false),
+            TokenType::Greater => (right, "__lt__", left, false),
+            TokenType::LessEqual => (right, "__lt__", left, true),
+            TokenType::GreaterEqual => (left, "__lt__", right, true),
+            _ => return Ok(None),
+        };
+
+        match self.call_special(receiver, name, vec![argument.clone()], operator)? {
+            Some(result) if negate => Ok(Some(Value::Boolean(!self.is_truthy(result)))),
+            result => Ok(result),
+        }
+    }
+
+    /// Calls the method `name` of `receiver` if it's an instance whose class defines it.
+    pub fn call_special(
+        &mut self,
+        receiver: &Value,
+        name: &str,
+        arguments: Vec<Value>,
+        token: &Token,
+    ) -> Result<Option<Value>, InterpreterError> {
+        let Value::Instance(instance) = receiver else {
+            return Ok(None);
+        };
+        let Some(method) = instance.class.find_method(name) else {
+            return Ok(None);
+        };
+
+        let bound = Value::Function(Rc::new(RefCell::new(method.bind(receiver.clone()))));
+        self.call_value(bound, arguments, token.clone()).map(Some)
+    }
+
+    /// The text `print` shows for a value, where an instance's `__str__` method may provide
+    /// the text for it, including inside lists, maps and variants.
+    pub fn stringify(&mut self, value: &Value) -> Result<String, InterpreterError> {
+        let mut instances = InstanceText::new();
+        self.instance_text(value, &mut Vec::new(), &mut instances)?;
+
+        Ok(value.to_string_with(&instances))
+    }
+
+    /// Calls `__str__` on the instances within `value` that define it, skipping the lists
+    /// and maps in `visiting` that are already being walked.
+    fn instance_text(
+        &mut self,
+        value: &Value,
+        visiting: &mut Vec<*const ()>,
+        instances: &mut InstanceText,
+    ) -> Result<(), InterpreterError> {
+        // The elements are copied out first, as a `__str__` method may change the container.
+        let (pointer, elements): (*const (), Vec<Value>) = match value {
+            Value::List(values) => (Rc::as_ptr(values) as *const (), values.borrow().clone()),
+            Value::Map(entries) => (
+                Rc::as_ptr(entries) as *const (),
+                entries.borrow().values().cloned().collect(),
+            ),
+            Value::Variant(variant) => {
+                for value in &variant.values {
+                    self.instance_text(value, visiting, instances)?;
+                }
+
+                return Ok(());
+            }
+            Value::Instance(instance) => {
+                let pointer = Rc::as_ptr(instance) as *const ();
+                let Some(method) = instance.class.find_method("__str__") else {
+                    return Ok(());
+                };
+                if instances.contains_key(&pointer) {
+                    return Ok(());
+                }
+
+                return match self.call_special(value, "__str__", Vec::new(), &method.name)? {
+                    Some(Value::String(text)) => {
+                        instances.insert(pointer, text.to_string());
+                        Ok(())
+                    }
+                    _ => Err(InterpreterError {
+                        token: Some(method.name.clone()),
+                        message: "'__str__' must return a string.".into(),
+                        kind: ErrorKind::Runtime,
+                    }),
+                };
+            }
+            _ => return Ok(()),
+        };
+
+        if visiting.contains(&pointer) {
+            return Ok(());
+        }
+
+        visiting.push(pointer);
+        for element in &elements {
+            self.instance_text(element, visiting, instances)?;
+        }
+        visiting.pop();
+
+        Ok(())
+    }
+
+    /// Evaluates `expression` with `environment` as the current scope.
+    pub fn evaluate_in(
+        &mut self,
+        expression: Expression,
+        environment: Environment,
+    ) -> EvaluateInterpreterResult {
+        let previous = self.environment.clone();
+        self.environment = environment;
+
+        let result = self.evaluate(expression);
+
+        self.environment = previous;
+        result
+    }
+
+    /// Evaluates the elements of an argument list or list literal, flattening spreads.
+    pub fn evaluate_elements(
+        &mut self,
+        elements: Vec<Expression>,
+    ) -> Result<Vec<Value>, InterpreterError> {
+        let mut values: Vec<Value> = Vec::new();
+
+        for element in elements {
+            match element {
+                Expression::Spread { operator, right } => {
+                    let iterable = self.evaluate(*right)?;
+                    let mut iterator = LoxIterator::new(iterable, self, &operator)?;
+
+                    while let Some(value) = iterator.next(self, &operator)? {
+                        values.push(value);
+                    }
                 }
+                element => values.push(self.evaluate(element)?),
             }
         }
+
+        Ok(values)
+    }
+
+    pub fn call_value(
+        &mut self,
+        callee: Value,
+        arguments: Vec<Value>,
+        parenthesis: Token,
+    ) -> EvaluateInterpreterResult {
+        let callable: Rc<RefCell<dyn Callable>> = match callee {
+            Value::Function(callable) => callable,
+            Value::Class(class) => Rc::new(RefCell::new(class)),
+            _ => {
+                return Err(InterpreterError {
+                    token: Some(parenthesis.clone()),
+                    message: "Can only call functions and classes.".into(),
+                    kind: ErrorKind::Runtime,
+                })
+            }
+        };
+
+        let arity = callable.borrow().arity();
+        let max_arity = callable.borrow().max_arity();
+        let count = arguments.len();
+
+        if count < arity || max_arity.is_some_and(|max_arity| count > max_arity) {
+            let expected = match max_arity {
+                Some(max_arity) if max_arity == arity => format!("{arity}"),
+                Some(max_arity) => format!("{arity} to {max_arity}"),
+                None => format!("at least {arity}"),
+            };
+
+            return Err(InterpreterError {
+                token: Some(parenthesis.clone()),
+                message: format!("Expected {expected} arguments but got {count}."),
+                kind: ErrorKind::Runtime,
+            });
+        }
+
+        let returned_value = callable.borrow().call(self, arguments, parenthesis)?;
+        Ok(returned_value.unwrap_or(Value::Nil))
+    }
+
+    pub fn get_index(
+        &mut self,
+        object: Value,
+        index: Value,
+        bracket: &Token,
+    ) -> EvaluateInterpreterResult {
+        match object {
+            Value::List(list) => {
+                let list = list.borrow();
+                let position = self.check_index(&index, list.len(), bracket)?;
+
+                Ok(list[position].clone())
+            }
+            Value::Map(map) => {
+                let key = self.check_key(&index, bracket)?;
+
+                Ok(map
+                    .borrow()
+                    .get(key.as_str())
+                    .cloned()
+                    .unwrap_or(Value::Nil))
+            }
+            Value::Instance(_) => {
+                match self.call_special(&object, "__getitem__", vec![index], bracket)? {
+                    Some(value) => Ok(value),
+                    None => Err(InterpreterError {
+                        token: Some(bracket.clone()),
+                        message:
+                            "Only lists, maps and instances with '__getitem__' can be indexed."
+                                .into(),
+                        kind: ErrorKind::Runtime,
+                    }),
+                }
+            }
+            _ => Err(InterpreterError {
+                token: Some(bracket.clone()),
+                message: "Only lists and maps can be indexed.".into(),
+                kind: ErrorKind::Runtime,
+            }),
+        }
+    }
+
+    pub fn set_index(
+        &mut self,
+        object: Value,
+        index: Value,
+        value: Value,
+        bracket: &Token,
+    ) -> Result<(), InterpreterError> {
+        match object {
+            Value::List(list) => {
+                let position = self.check_index(&index, list.borrow().len(), bracket)?;
+                list.borrow_mut()[position] = value;
+
+                Ok(())
+            }
+            Value::Map(map) => {
+                let key = self.check_key(&index, bracket)?;
+                map.borrow_mut().insert(key.to_string(), value);
+
+                Ok(())
+            }
+            Value::Instance(_) => {
+                match self.call_special(&object, "__setitem__", vec![index, value], bracket)? {
+                    Some(_) => Ok(()),
+                    None => Err(InterpreterError {
+                        token: Some(bracket.clone()),
+                        message: "Only lists, maps and instances with '__setitem__' can be assigned by index.".into(),
+                        kind: ErrorKind::Runtime,
+                    }),
+                }
+            }
+            _ => Err(InterpreterError {
+                token: Some(bracket.clone()),
+                message: "Only lists and maps can be indexed.".into(),
+                kind: ErrorKind::Runtime,
+            }),
+        }
+    }
+
+    pub fn check_key(&self, key: &Value, bracket: &Token) -> Result<Rc<String>, InterpreterError> {
+        match key {
+            Value::String(key) => Ok(key.clone()),
+            _ => Err(InterpreterError {
+                token: Some(bracket.clone()),
+                message: "Map keys must be strings.".into(),
+                kind: ErrorKind::Runtime,
+            }),
+        }
+    }
+
+    pub fn check_index(
+        &self,
+        index: &Value,
+        length: usize,
+        bracket: &Token,
+    ) -> Result<usize, InterpreterError> {
+        let Value::Number(index) = index else {
+            return Err(InterpreterError {
+                token: Some(bracket.clone()),
+                message: "Index must be an integer.".into(),
+                kind: ErrorKind::Runtime,
+            });
+        };
+
+        if index.fract() != 0.0 {
+            return Err(InterpreterError {
+                token: Some(bracket.clone()),
+                message: "Index must be an integer.".into(),
+                kind: ErrorKind::Runtime,
+            });
+        }
+
+        if *index < 0.0 || *index >= length as f64 {
+            return Err(InterpreterError {
+                token: Some(bracket.clone()),
+                message: format!("Index {index} is out of bounds for length {length}."),
+                kind: ErrorKind::Runtime,
+            });
+        }
+
+        Ok(*index as usize)
+    }
+
+    pub fn get_property(&mut self, object: Value, name: &Token) -> EvaluateInterpreterResult {
+        match object {
+            Value::Error(error) => match name.lexeme.as_str() {
+                "message" => Ok(Value::String(Rc::new(error.message.clone()))),
+                "line" => Ok(error
+                    .line
+                    .map_or(Value::Nil, |line| Value::Number(line as f64))),
+                "assertion" => Ok(Value::Boolean(error.assertion)),
+                _ => Err(InterpreterError {
+                    token: Some(name.clone()),
+                    message: format!("Undefined property '{}'.", name.lexeme),
+                    kind: ErrorKind::Runtime,
+                }),
+            },
+            Value::Map(map) => match map.borrow().get(&name.lexeme) {
+                Some(value) => Ok(value.clone()),
+                None => Err(InterpreterError {
+                    token: Some(name.clone()),
+                    message: format!("Undefined property '{}'.", name.lexeme),
+                    kind: ErrorKind::Runtime,
+                }),
+            },
+            Value::Generator(generator) if name.lexeme == "next" => {
+                Ok(Value::Function(Rc::new(RefCell::new(GeneratorNext {
+                    generator,
+                }))))
+            }
+            Value::Enum(enumeration) => {
+                let fields = enumeration
+                    .variants
+                    .iter()
+                    .find(|(tag, _)| *tag == name.lexeme)
+                    .map(|(_, fields)| fields.len());
+
+                match fields {
+                    Some(0) => Ok(Value::Variant(Rc::new(LoxVariant {
+                        enumeration: enumeration.clone(),
+                        tag: name.lexeme.clone(),
+                        values: Vec::new(),
+                    }))),
+                    Some(arity) => Ok(Value::Function(Rc::new(RefCell::new(VariantConstructor {
+                        enumeration: enumeration.clone(),
+                        tag: name.lexeme.clone(),
+                        arity,
+                    })))),
+                    None => Err(InterpreterError {
+                        token: Some(name.clone()),
+                        message: format!(
+                            "Enum '{}' has no variant '{}'.",
+                            enumeration.name, name.lexeme
+                        ),
+                        kind: ErrorKind::Runtime,
+                    }),
+                }
+            }
+            Value::Variant(variant) => match variant.field(&name.lexeme) {
+                Some(value) => Ok(value),
+                None => Err(InterpreterError {
+                    token: Some(name.clone()),
+                    message: format!("Undefined property '{}'.", name.lexeme),
+                    kind: ErrorKind::Runtime,
+                }),
+            },
+            Value::Instance(ref instance) => {
+                if let Some(value) = instance.field(&name.lexeme) {
+                    return Ok(value);
+                }
+
+                if let Some(getter) = instance.class.find_getter(&name.lexeme) {
+                    let getter =
+                        Value::Function(Rc::new(RefCell::new(getter.bind(object.clone()))));
+                    return self.call_value(getter, Vec::new(), name.clone());
+                }
+
+                match instance.class.find_method(&name.lexeme) {
+                    Some(method) => Ok(Value::Function(Rc::new(RefCell::new(
+                        method.bind(object.clone()),
+                    )))),
+                    None => Err(InterpreterError {
+                        token: Some(name.clone()),
+                        message: format!("Undefined property '{}'.", name.lexeme),
+                        kind: ErrorKind::Runtime,
+                    }),
+                }
+            }
+            Value::Class(ref class) => match class.find_static(&name.lexeme) {
+                Some(method) => Ok(Value::Function(Rc::new(RefCell::new(
+                    method.bind(object.clone()),
+                )))),
+                None => Err(InterpreterError {
+                    token: Some(name.clone()),
+                    message: format!("Undefined property '{}'.", name.lexeme),
+                    kind: ErrorKind::Runtime,
+                }),
+            },
+            Value::Module(module) => match module.environment.get_local(&name.lexeme) {
+                Some(value) => Ok(value),
+                None => Err(InterpreterError {
+                    token: Some(name.clone()),
+                    message: format!("Undefined property '{}'.", name.lexeme),
+                    kind: ErrorKind::Runtime,
+                }),
+            },
+            _ => Err(InterpreterError {
+                token: Some(name.clone()),
+                message: "Only objects have properties.".into(),
+                kind: ErrorKind::Runtime,
+            }),
+        }
+    }
+
+    pub fn set_property(
+        &mut self,
+        object: Value,
+        name: &Token,
+        value: Value,
+    ) -> Result<(), InterpreterError> {
+        match object {
+            Value::Map(map) => {
+                map.borrow_mut().insert(name.lexeme.clone(), value);
+
+                Ok(())
+            }
+            Value::Instance(ref instance) => {
+                if let Some(setter) = instance.class.find_setter(&name.lexeme) {
+                    let setter =
+                        Value::Function(Rc::new(RefCell::new(setter.bind(object.clone()))));
+                    self.call_value(setter, vec![value], name.clone())?;
+
+                    return Ok(());
+                }
+
+                instance
+                    .fields
+                    .borrow_mut()
+                    .insert(name.lexeme.clone(), value);
+
+                Ok(())
+            }
+            _ => Err(InterpreterError {
+                token: Some(name.clone()),
+                message: "Only objects have fields.".into(),
+                kind: ErrorKind::Runtime,
+            }),
+        }
     }
 
     pub fn is_truthy(&self, value: Value) -> bool {
@@ -336,6 +1425,7 @@ impl Interpreter {
             _ => Err(InterpreterError {
                 token: Some(operator.clone()),
                 message: "Operand must be a number.".into(),
+                kind: ErrorKind::Runtime,
             }),
         }
     }
@@ -351,7 +1441,28 @@ impl Interpreter {
             _ => Err(InterpreterError {
                 token: Some(operator.clone()),
                 message: "Operands must be a number.".into(),
+                kind: ErrorKind::Runtime,
             }),
         }
     }
+
+    pub fn check_integer_operands(
+        &self,
+        operator: &Token,
+        left: &Value,
+        right: &Value,
+    ) -> Result<(i64, i64), InterpreterError> {
+        let (x, y) = self.check_number_operands(operator, left, right)?;
+
+        let is_integer = |value: f64| value.fract() == 0.0 && value.abs() <= i64::MAX as f64;
+        if !is_integer(x) || !is_integer(y) {
+            return Err(InterpreterError {
+                token: Some(operator.clone()),
+                message: "Operands must be integers.".into(),
+                kind: ErrorKind::Runtime,
+            });
+        }
+
+        Ok((x as i64, y as i64))
+    }
 }
@@ -0,0 +1,146 @@
+use std::{cell::RefCell, rc::Rc, vec::IntoIter};
+
+use crate::{resume_generator, ErrorKind, Generator, Interpreter, InterpreterError, Token, Value};
+
+/// The position of a `for (var x in ...)` loop within the value it iterates over.
+#[derive(Debug)]
+pub enum LoxIterator {
+    List {
+        list: Rc<RefCell<Vec<Value>>>,
+        index: usize,
+    },
+    String {
+        string: Rc<String>,
+        offset: usize,
+    },
+    Keys(IntoIter<String>),
+    Range {
+        next: f64,
+        end: f64,
+        step: f64,
+    },
+    /// An object, or an instance, whose `next()` returns a `{done, value}` pair.
+    Protocol(Value),
+    Generator(Rc<RefCell<Generator>>),
+}
+
+impl LoxIterator {
+    /// Starts iterating over `iterable`. An instance is iterated with its `next` method, or
+    /// else over what its `__iter__` method returns.
+    pub fn new(
+        iterable: Value,
+        interpreter: &mut Interpreter,
+        token: &Token,
+    ) -> Result<Self, InterpreterError> {
+        match iterable {
+            Value::List(list) => Ok(LoxIterator::List { list, index: 0 }),
+            Value::String(string) => Ok(LoxIterator::String { string, offset: 0 }),
+            Value::Map(map) => {
+                let next = map.borrow().get("next").cloned();
+
+                match next {
+                    Some(next @ Value::Function(_)) => Ok(LoxIterator::Protocol(next)),
+                    _ => Ok(LoxIterator::Keys(
+                        map.borrow()
+                            .keys()
+                            .cloned()
+                            .collect::<Vec<String>>()
+                            .into_iter(),
+                    )),
+                }
+            }
+            Value::Range(range) => Ok(LoxIterator::Range {
+                next: range.start,
+                end: range.end,
+                step: range.step,
+            }),
+            Value::Generator(generator) => Ok(LoxIterator::Generator(generator)),
+            Value::Instance(ref instance) => {
+                if let Some(next) = instance.class.find_method("next") {
+                    let next = next.bind(iterable.clone());
+                    return Ok(LoxIterator::Protocol(Value::Function(Rc::new(
+                        RefCell::new(next),
+                    ))));
+                }
+
+                let iterator = interpreter
+                    .call_special(&iterable, "__iter__", Vec::new(), token)?
+                    .ok_or_else(|| not_iterable(token))?;
+
+                // An `__iter__` returning the instance itself would otherwise recurse forever.
+                match &iterator {
+                    Value::Instance(other) if Rc::ptr_eq(instance, other) => {
+                        Err(not_iterable(token))
+                    }
+                    _ => LoxIterator::new(iterator, interpreter, token),
+                }
+            }
+            _ => Err(not_iterable(token)),
+        }
+    }
+
+    /// Produces the next value, or `None` once the iteration is over.
+    pub fn next(
+        &mut self,
+        interpreter: &mut Interpreter,
+        token: &Token,
+    ) -> Result<Option<Value>, InterpreterError> {
+        match self {
+            LoxIterator::List { list, index } => {
+                let value = list.borrow().get(*index).cloned();
+                *index += 1;
+
+                Ok(value)
+            }
+            LoxIterator::String { string, offset } => {
+                let character = string[*offset..].chars().next();
+
+                Ok(character.map(|character| {
+                    *offset += character.len_utf8();
+                    Value::String(Rc::new(character.to_string()))
+                }))
+            }
+            LoxIterator::Keys(keys) => Ok(keys.next().map(|key| Value::String(Rc::new(key)))),
+            LoxIterator::Range { next, end, step } => {
+                if (*step > 0.0 && *next >= *end) || (*step < 0.0 && *next <= *end) {
+                    return Ok(None);
+                }
+
+                let value = *next;
+                *next += *step;
+
+                Ok(Some(Value::Number(value)))
+            }
+            LoxIterator::Protocol(next) => {
+                let result = interpreter.call_value(next.clone(), Vec::new(), token.clone())?;
+
+                let Value::Map(result) = result else {
+                    return Err(InterpreterError {
+                        token: Some(token.clone()),
+                        message: "Iterator 'next' must return a map with 'done' and 'value'."
+                            .into(),
+                        kind: ErrorKind::Runtime,
+                    });
+                };
+
+                let result = result.borrow();
+                let done = result.get("done").cloned().unwrap_or(Value::Nil);
+
+                if interpreter.is_truthy(done) {
+                    return Ok(None);
+                }
+
+                Ok(Some(result.get("value").cloned().unwrap_or(Value::Nil)))
+            }
+            LoxIterator::Generator(generator) => resume_generator(generator, interpreter, token),
+        }
+    }
+}
+
+fn not_iterable(token: &Token) -> InterpreterError {
+    InterpreterError {
+        token: Some(token.clone()),
+        message: "Can only iterate over lists, strings, maps, ranges and iterators.".into(),
+        kind: ErrorKind::Runtime,
+    }
+}
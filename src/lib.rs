@@ -1,19 +1,42 @@
+pub mod capabilities;
+pub mod class;
+pub mod clock;
+pub mod enumeration;
 pub mod environment;
 pub mod expression;
 pub mod function;
+pub mod generator;
 pub mod grammar;
 pub mod interpreter;
+pub mod iterator;
+pub mod module;
 pub mod parser;
+pub mod pattern;
+pub mod random;
+pub mod regex;
 pub mod scanner;
 pub mod statement;
+pub mod stdlib;
+pub mod testing;
 pub mod value;
 
+pub use capabilities::*;
+pub use class::*;
+pub use clock::*;
+pub use enumeration::*;
 pub use environment::Environment;
 pub use expression::*;
 pub use function::*;
+pub use generator::*;
 pub use grammar::*;
 pub use interpreter::*;
+pub use iterator::*;
+pub use module::*;
 pub use parser::*;
+pub use pattern::*;
+pub use random::*;
+pub use regex::*;
 pub use scanner::*;
 pub use statement::*;
+pub use testing::*;
 pub use value::*;
@@ -1,24 +1,85 @@
 use std::env;
 use std::fs;
 use std::io::{self, Write};
+use std::path::PathBuf;
 use std::process::exit;
+use std::time::Duration;
 
-use codecrafters_interpreter::{Interpreter, Value, Parser, Scanner};
+use codecrafters_interpreter::{discover_tests, run_tests, Capabilities, ErrorKind, FakeClock, Interpreter, Random, Value, Parser, Scanner};
+
+/// Removes `flag` and the value following it from `args`, returning the value. Options go
+/// between the command and the filename, so the script's own arguments are left alone.
+fn take_option(args: &mut Vec<String>, flag: &str) -> Option<String> {
+    let mut index = 2;
+
+    while index < args.len() && args[index].starts_with("--") {
+        if args[index] != flag {
+            index += 2;
+            continue;
+        }
+
+        if index + 1 >= args.len() {
+            eprintln!("Missing value for {}", flag);
+            exit(64);
+        }
+
+        args.remove(index);
+        return Some(args.remove(index));
+    }
+
+    None
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+
+    // Pins the clock natives to this many seconds after the Unix epoch.
+    let fake_clock = take_option(&mut args, "--fake-clock").map(|seconds| {
+        match seconds.parse::<f64>().ok().and_then(|seconds| Duration::try_from_secs_f64(seconds).ok()) {
+            Some(start) => start,
+            None => {
+                eprintln!("Invalid value for --fake-clock: {}", seconds);
+                exit(64);
+            }
+        }
+    });
+
+    let seed = take_option(&mut args, "--seed").map(|seed| {
+        seed.parse::<u64>().unwrap_or_else(|_| {
+            eprintln!("Invalid value for --seed: {}", seed);
+            exit(64);
+        })
+    });
+
+    // Every known option has been taken, so whatever flag is left is a mistake.
+    if let Some(option) = args.get(2).filter(|arg| arg.starts_with("--")) {
+        eprintln!("Unknown option: {}", option);
+        exit(64);
+    }
+
     if args.len() < 3 {
-        writeln!(io::stderr(), "Usage: {} tokenize <filename>", args[0]).unwrap();
+        eprintln!("Usage: {} tokenize <filename>", args[0]);
         return;
     }
 
     let command = &args[1];
     let filename = &args[2];
 
+    // Applies the options to an interpreter about to run a script or its tests.
+    let setup = |interpreter: &mut Interpreter| {
+        if let Some(start) = fake_clock {
+            interpreter.clock = Box::new(FakeClock::new(start));
+        }
+
+        if let Some(seed) = seed {
+            interpreter.random = Random::new(seed);
+        }
+    };
+
     match command.as_str() {
         "tokenize" => {
             let file_contents = fs::read_to_string(filename).unwrap_or_else(|_| {
-                writeln!(io::stderr(), "Failed to read file {}", filename).unwrap();
+                eprintln!("Failed to read file {}", filename);
                 String::new()
             });
 
@@ -35,7 +96,7 @@ fn main() {
         }
         "parse" => {
             let file_contents = fs::read_to_string(filename).unwrap_or_else(|_| {
-                writeln!(io::stderr(), "Failed to read file {}", filename).unwrap();
+                eprintln!("Failed to read file {}", filename);
                 String::new()
             });
 
@@ -57,7 +118,7 @@ fn main() {
         }
         "evaluate" => {
             let file_contents = fs::read_to_string(filename).unwrap_or_else(|_| {
-                writeln!(io::stderr(), "Failed to read file {}", filename).unwrap();
+                eprintln!("Failed to read file {}", filename);
                 String::new()
             });
 
@@ -84,6 +145,11 @@ fn main() {
                     _ => println!("{}", value),
                 },
                 Err(error) => {
+                    if let ErrorKind::Exit(code) = error.kind {
+                        let _ = io::stdout().flush();
+                        exit(code);
+                    }
+
                     eprintln!("{error}");
 
                     if let Some(token) = error.token {
@@ -96,7 +162,7 @@ fn main() {
         }
         "run" => {
             let file_contents = fs::read_to_string(filename).unwrap_or_else(|_| {
-                writeln!(io::stderr(), "Failed to read file {}", filename).unwrap();
+                eprintln!("Failed to read file {}", filename);
                 String::new()
             });
 
@@ -117,9 +183,20 @@ fn main() {
             };
 
             let mut interpreter = Interpreter::new();
+            interpreter.current_file = Some(PathBuf::from(filename));
+            interpreter.capabilities = Capabilities::all();
+            interpreter.set_arguments(args[3..].to_vec());
+
+            setup(&mut interpreter);
+
             match interpreter.interpret(statements) {
                 Ok(_) => {},
                 Err(error) => {
+                    if let ErrorKind::Exit(code) = error.kind {
+                        let _ = io::stdout().flush();
+                        exit(code);
+                    }
+
                     eprintln!("{error}");
 
                     if let Some(token) = error.token {
@@ -130,9 +207,41 @@ fn main() {
                 }
             }
         }
+        "test" => {
+            let paths: Vec<PathBuf> = args[2..].iter().map(PathBuf::from).collect();
+
+            let files = discover_tests(&paths).unwrap_or_else(|error| {
+                eprintln!("Failed to discover tests: {}", error);
+                exit(1);
+            });
+
+            let (mut passed, mut failed) = (0, 0);
+
+            for file in files {
+                for outcome in run_tests(&file, setup) {
+                    match outcome.failure {
+                        None => {
+                            passed += 1;
+                            println!("PASS {}::{}", outcome.file.display(), outcome.name);
+                        }
+                        Some(message) => {
+                            failed += 1;
+                            println!("FAIL {}::{}", outcome.file.display(), outcome.name);
+                            println!("    {}", message);
+                        }
+                    }
+                }
+            }
+
+            println!();
+            println!("{} passed, {} failed", passed, failed);
+
+            if failed > 0 {
+                exit(1);
+            }
+        }
         _ => {
-            writeln!(io::stderr(), "Unknown command: {}", command).unwrap();
-            return;
+            eprintln!("Unknown command: {}", command);
         }
     }
 }
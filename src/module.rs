@@ -0,0 +1,111 @@
+use std::{fs, path::PathBuf, rc::Rc};
+
+use crate::{Environment, ErrorKind, Interpreter, InterpreterError, Parser, Scanner, Token};
+
+/// The namespace created by executing an imported file.
+#[derive(Debug, PartialEq)]
+pub struct LoxModule {
+    pub path: PathBuf,
+    pub environment: Environment,
+}
+
+impl Interpreter {
+    /// Scans, parses and executes the module at `path` (relative to the importing file),
+    /// or returns the cached namespace if it was already imported. Like the file natives,
+    /// it may only read files within the capabilities' roots.
+    pub fn import_module(
+        &mut self,
+        keyword: &Token,
+        path: &str,
+    ) -> Result<Rc<LoxModule>, InterpreterError> {
+        let base = self
+            .current_file
+            .as_ref()
+            .and_then(|file| file.parent())
+            .map(PathBuf::from)
+            .unwrap_or_default();
+
+        let resolve_error = |error| InterpreterError {
+            token: Some(keyword.clone()),
+            message: format!("Could not resolve module '{path}': {error}."),
+            kind: ErrorKind::Runtime,
+        };
+
+        let resolved = base.join(path).canonicalize().map_err(resolve_error)?;
+        let Some(path) = self
+            .capabilities
+            .check_path(&resolved.to_string_lossy())
+            .map_err(resolve_error)?
+        else {
+            return Err(InterpreterError {
+                token: Some(keyword.clone()),
+                message: format!("Access to '{path}' is not allowed."),
+                kind: ErrorKind::Runtime,
+            });
+        };
+
+        if let Some(module) = self.modules.get(&path) {
+            return Ok(module.clone());
+        }
+
+        if let Some(start) = self.importing.iter().position(|file| *file == path) {
+            let cycle = self.importing[start..]
+                .iter()
+                .chain([&path])
+                .map(|file| file.display().to_string())
+                .collect::<Vec<String>>()
+                .join(" -> ");
+
+            return Err(InterpreterError {
+                token: Some(keyword.clone()),
+                message: format!("Import cycle detected: {cycle}."),
+                kind: ErrorKind::Runtime,
+            });
+        }
+
+        let source = fs::read_to_string(&path).map_err(|error| InterpreterError {
+            token: Some(keyword.clone()),
+            message: format!("Could not read module '{}': {error}.", path.display()),
+            kind: ErrorKind::Runtime,
+        })?;
+
+        let mut scanner = Scanner::new(source);
+        let tokens = scanner.scan_tokens();
+
+        if scanner.had_error {
+            return Err(InterpreterError {
+                token: Some(keyword.clone()),
+                message: format!("Could not scan module '{}'.", path.display()),
+                kind: ErrorKind::Runtime,
+            });
+        }
+
+        let statements = Parser::new(tokens)
+            .parse()
+            .map_err(|error| InterpreterError {
+                token: Some(keyword.clone()),
+                message: format!("In module '{}': {error}", path.display()),
+                kind: ErrorKind::Runtime,
+            })?;
+
+        let environment = self.builtins.enclose();
+
+        self.importing.push(path.clone());
+        let previous = self.current_file.replace(path.clone());
+
+        let result = self.execute_block(statements, environment.clone());
+
+        self.current_file = previous;
+        self.importing.pop();
+
+        result?;
+
+        let module = Rc::new(LoxModule {
+            path: path.clone(),
+            environment,
+        });
+        self.modules.insert(path, module.clone());
+
+        Ok(module)
+    }
+}
@@ -1,11 +1,27 @@
-use std::vec::Vec;
+use std::{
+    collections::{BTreeSet, HashMap},
+    vec::Vec,
+};
 
-use crate::{Expression, Literal, Statement, Token, TokenType};
+use crate::{
+    Catch, Expression, Literal, MatchArm, Method, MethodKind, Parameter, Pattern, Statement, Token,
+    TokenType, Variant,
+};
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Parser {
     tokens: Vec<Token>,
     current: usize,
+    /// Names declared in each enclosing scope, mapped to whether they are constant.
+    scopes: Vec<HashMap<String, bool>>,
+    /// Index in `scopes` of the outermost scope of each enclosing function.
+    functions: Vec<usize>,
+    /// Whether each enclosing function is a generator.
+    generators: Vec<bool>,
+    /// Whether each enclosing function is a class's `init` method.
+    initializers: Vec<bool>,
+    /// Whether each enclosing class has a superclass.
+    classes: Vec<bool>,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -17,7 +33,15 @@ type ExpressionParserResult = Result<Expression, ParseError>;
 
 impl Parser {
     pub fn new(tokens: Vec<Token>) -> Self {
-        Parser { tokens, current: 0 }
+        Parser {
+            tokens,
+            current: 0,
+            scopes: vec![HashMap::new()],
+            functions: Vec::new(),
+            generators: Vec::new(),
+            initializers: Vec::new(),
+            classes: Vec::new(),
+        }
     }
 
     pub fn parse(&mut self) -> Result<Vec<Statement>, ParseError> {
@@ -40,10 +64,80 @@ impl Parser {
             return self.variable();
         }
 
+        if self.match_(&[&TokenType::Const]) {
+            return self.constant();
+        }
+
+        if self.match_(&[&TokenType::Enum]) {
+            return self.enumeration();
+        }
+
+        if self.match_(&[&TokenType::Class]) {
+            return self.class();
+        }
+
+        if self.check_contextual("trait") && self.peek_at(1).token_type == TokenType::Identifier {
+            self.advance();
+            return self.trait_();
+        }
+
+        if self.match_(&[&TokenType::Import]) {
+            return self.import();
+        }
+
         self.statement()
     }
 
+    pub fn import(&mut self) -> StatementParserResult {
+        let keyword = self.previous().clone();
+
+        let mut names: Vec<Token> = Vec::new();
+        if self.match_(&[&TokenType::LeftBrace]) {
+            loop {
+                names.push(
+                    self.consume(&TokenType::Identifier, "Expect imported name.")?
+                        .clone(),
+                );
+
+                if !self.match_(&[&TokenType::Comma]) {
+                    break;
+                }
+            }
+
+            self.consume(&TokenType::RightBrace, "Expect '}' after imported names.")?;
+            self.consume_contextual("from", "Expect 'from' after imported names.")?;
+        }
+
+        let path = self
+            .consume(&TokenType::String, "Expect module path.")?
+            .clone();
+
+        let mut alias: Option<Token> = None;
+        if names.is_empty() && self.check_contextual("as") {
+            self.advance();
+            alias = Some(
+                self.consume(&TokenType::Identifier, "Expect module name after 'as'.")?
+                    .clone(),
+            );
+        }
+
+        self.consume(&TokenType::Semicolon, "Expect ';' after import.")?;
+
+        for name in names.iter().chain(alias.iter()) {
+            self.declare(name, false);
+        }
+
+        Ok(Statement::Import {
+            keyword,
+            path,
+            alias,
+            names,
+        })
+    }
+
     pub fn function(&mut self, kind: &str) -> StatementParserResult {
+        let generator = self.match_(&[&TokenType::Star]);
+
         let name = self
             .consume(
                 &TokenType::Identifier,
@@ -51,30 +145,78 @@ impl Parser {
             )?
             .clone();
 
-        self.consume(
-            &TokenType::LeftParen,
-            format!("Expect '(' after {kind} name.").as_str(),
-        )?;
+        // A getter has no parameter list.
+        if kind != "getter" {
+            self.consume(
+                &TokenType::LeftParen,
+                format!("Expect '(' after {kind} name.").as_str(),
+            )?;
+        }
 
-        let mut parameters: Vec<Token> = Vec::new();
-        if !self.check(&TokenType::RightParen) {
-            loop {
-                if parameters.len() >= 255 {
-                    return Err(self.error(self.peek(), "Can't have more than 255 parameters."));
-                }
+        let initializer = kind == "method" && name.lexeme == "init";
+        if initializer && generator {
+            return Err(self.error(&name, "An initializer can't be a generator."));
+        }
 
-                parameters.push(
-                    self.consume(&TokenType::Identifier, "Expect parameter name.")?
-                        .clone(),
-                );
+        // Methods are looked up on their class, so they don't bind their name in the scope.
+        if kind == "function" {
+            self.declare(&name, false);
+        }
 
-                if !self.match_(&[&TokenType::Comma]) {
-                    break;
+        self.begin_scope();
+        self.functions.push(self.scopes.len() - 1);
+        self.generators.push(generator);
+        self.initializers.push(initializer);
+
+        let mut parameters: Vec<Parameter> = Vec::new();
+        let mut rest: Option<Token> = None;
+        if kind != "getter" {
+            if !self.check(&TokenType::RightParen) {
+                loop {
+                    if parameters.len() >= 255 {
+                        return Err(self.error(self.peek(), "Can't have more than 255 parameters."));
+                    }
+
+                    if self.match_(&[&TokenType::DotDotDot]) {
+                        let name = self
+                            .consume(&TokenType::Identifier, "Expect rest parameter name.")?
+                            .clone();
+
+                        self.declare(&name, false);
+                        rest = Some(name);
+
+                        // The rest parameter collects everything left, so it must come last.
+                        break;
+                    }
+
+                    let name = self
+                        .consume(&TokenType::Identifier, "Expect parameter name.")?
+                        .clone();
+
+                    let mut default: Option<Expression> = None;
+                    if self.match_(&[&TokenType::Equal]) {
+                        default = Some(self.expression()?);
+                    } else if parameters
+                        .iter()
+                        .any(|parameter| parameter.default.is_some())
+                    {
+                        return Err(self.error(
+                            &name,
+                            "Parameter without a default value can't follow one with a default.",
+                        ));
+                    }
+
+                    self.declare(&name, false);
+                    parameters.push(Parameter { name, default });
+
+                    if !self.match_(&[&TokenType::Comma]) {
+                        break;
+                    }
                 }
             }
-        }
 
-        self.consume(&TokenType::RightParen, "Expect ')' after parameters.")?;
+            self.consume(&TokenType::RightParen, "Expect ')' after parameters.")?;
+        }
         self.consume(
             &TokenType::LeftBrace,
             format!("Expect '{{' before {kind} body.").as_str(),
@@ -82,14 +224,213 @@ impl Parser {
 
         let body = self.block()?;
 
+        self.initializers.pop();
+        self.generators.pop();
+        self.functions.pop();
+        self.end_scope();
+
         Ok(Statement::Function {
             name,
             parameters,
+            rest,
             body,
+            generator,
         })
     }
 
+    pub fn enumeration(&mut self) -> StatementParserResult {
+        let name = self
+            .consume(&TokenType::Identifier, "Expect enum name.")?
+            .clone();
+        self.consume(&TokenType::LeftBrace, "Expect '{' before enum variants.")?;
+
+        let mut variants: Vec<Variant> = Vec::new();
+        while !self.check(&TokenType::RightBrace) {
+            let variant = self
+                .consume(&TokenType::Identifier, "Expect variant name.")?
+                .clone();
+
+            if variants
+                .iter()
+                .any(|other| other.name.lexeme == variant.lexeme)
+            {
+                return Err(self.error(&variant, "Duplicate variant name."));
+            }
+
+            let mut fields: Vec<Token> = Vec::new();
+            if self.match_(&[&TokenType::LeftParen]) {
+                loop {
+                    fields.push(
+                        self.consume(&TokenType::Identifier, "Expect field name.")?
+                            .clone(),
+                    );
+
+                    if !self.match_(&[&TokenType::Comma]) {
+                        break;
+                    }
+                }
+
+                self.consume(&TokenType::RightParen, "Expect ')' after variant fields.")?;
+            }
+
+            variants.push(Variant {
+                name: variant,
+                fields,
+            });
+
+            if !self.match_(&[&TokenType::Comma]) {
+                break;
+            }
+        }
+
+        self.consume(&TokenType::RightBrace, "Expect '}' after enum variants.")?;
+
+        self.declare(&name, false);
+
+        Ok(Statement::Enum { name, variants })
+    }
+
+    pub fn class(&mut self) -> StatementParserResult {
+        let name = self
+            .consume(&TokenType::Identifier, "Expect class name.")?
+            .clone();
+
+        let mut superclass: Option<Expression> = None;
+        if self.match_(&[&TokenType::Less]) {
+            if self.peek().lexeme == name.lexeme {
+                return Err(self.error(self.peek(), "A class can't inherit from itself."));
+            }
+
+            superclass = Some(self.qualified_name("superclass")?);
+        }
+
+        let mut traits: Vec<Expression> = Vec::new();
+        if self.check_contextual("with") {
+            self.advance();
+
+            loop {
+                traits.push(self.qualified_name("trait")?);
+
+                if !self.match_(&[&TokenType::Comma]) {
+                    break;
+                }
+            }
+        }
+
+        self.declare(&name, false);
+        self.consume(&TokenType::LeftBrace, "Expect '{' before class body.")?;
+
+        self.classes.push(superclass.is_some());
+
+        let mut methods: Vec<Method> = Vec::new();
+        while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
+            methods.push(self.method()?);
+        }
+
+        self.classes.pop();
+
+        self.consume(&TokenType::RightBrace, "Expect '}' after class body.")?;
+
+        Ok(Statement::Class {
+            name,
+            superclass,
+            traits,
+            methods,
+        })
+    }
+
+    /// A name, or a member of an imported module such as `shapes.Shape`.
+    pub fn qualified_name(&mut self, kind: &str) -> ExpressionParserResult {
+        let name = self
+            .consume(
+                &TokenType::Identifier,
+                format!("Expect {kind} name.").as_str(),
+            )?
+            .clone();
+
+        let mut expression = Expression::Variable(name);
+        while self.match_(&[&TokenType::Dot]) {
+            let name = self
+                .consume(
+                    &TokenType::Identifier,
+                    format!("Expect {kind} name after '.'.").as_str(),
+                )?
+                .clone();
+
+            expression = Expression::Get {
+                object: Box::new(expression),
+                name,
+            };
+        }
+
+        Ok(expression)
+    }
+
+    /// `trait Name { ... }`, a set of methods that classes can take in with `with`.
+    pub fn trait_(&mut self) -> StatementParserResult {
+        let name = self
+            .consume(&TokenType::Identifier, "Expect trait name.")?
+            .clone();
+
+        self.declare(&name, false);
+        self.consume(&TokenType::LeftBrace, "Expect '{' before trait body.")?;
+
+        self.classes.push(false);
+
+        let mut methods: Vec<Method> = Vec::new();
+        while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
+            methods.push(self.method()?);
+        }
+
+        self.classes.pop();
+
+        self.consume(&TokenType::RightBrace, "Expect '}' after trait body.")?;
+
+        Ok(Statement::Trait { name, methods })
+    }
+
+    /// A method, `class` method, getter without a parameter list, or `set` method.
+    pub fn method(&mut self) -> Result<Method, ParseError> {
+        let kind = if self.match_(&[&TokenType::Class]) {
+            MethodKind::Static
+        } else if self.check_contextual("set")
+            && self.peek_at(1).token_type == TokenType::Identifier
+        {
+            self.advance();
+            MethodKind::Setter
+        } else if self.peek_at(1).token_type == TokenType::LeftBrace {
+            MethodKind::Getter
+        } else {
+            MethodKind::Method
+        };
+
+        let function = self.function(match kind {
+            MethodKind::Method => "method",
+            MethodKind::Getter => "getter",
+            MethodKind::Setter => "setter",
+            MethodKind::Static => "class method",
+        })?;
+
+        if let Statement::Function {
+            name,
+            parameters,
+            rest,
+            ..
+        } = &function
+        {
+            if kind == MethodKind::Setter && (parameters.len() != 1 || rest.is_some()) {
+                return Err(self.error(name, "A setter must have exactly one parameter."));
+            }
+        }
+
+        Ok(Method { kind, function })
+    }
+
     pub fn statement(&mut self) -> StatementParserResult {
+        if self.match_(&[&TokenType::Assert]) {
+            return self.assert();
+        }
+
         if self.match_(&[&TokenType::For]) {
             return self.for_();
         }
@@ -98,6 +439,10 @@ impl Parser {
             return self.if_();
         }
 
+        if self.match_(&[&TokenType::Match]) {
+            return self.match_statement();
+        }
+
         if self.match_(&[&TokenType::Print]) {
             return self.print();
         }
@@ -106,6 +451,18 @@ impl Parser {
             return self.return_();
         }
 
+        if self.match_(&[&TokenType::Throw]) {
+            return self.throw();
+        }
+
+        if self.match_(&[&TokenType::Try]) {
+            return self.try_();
+        }
+
+        if self.match_(&[&TokenType::Yield]) {
+            return self.yield_();
+        }
+
         if self.match_(&[&TokenType::While]) {
             return self.while_();
         }
@@ -120,6 +477,15 @@ impl Parser {
     pub fn for_(&mut self) -> StatementParserResult {
         self.consume(&TokenType::LeftParen, "Expect '(' after 'for'.")?;
 
+        if self.check(&TokenType::Var)
+            && self.peek_at(2).token_type == TokenType::Identifier
+            && self.peek_at(2).lexeme == "in"
+        {
+            return self.for_in();
+        }
+
+        self.begin_scope();
+
         let initializer: Option<Statement>;
         if self.match_(&[&TokenType::Semicolon]) {
             initializer = None;
@@ -158,9 +524,33 @@ impl Parser {
             body = Statement::Block(vec![expression, body]);
         }
 
+        self.end_scope();
+
         Ok(body)
     }
 
+    pub fn for_in(&mut self) -> StatementParserResult {
+        self.advance();
+
+        let name = self
+            .consume(&TokenType::Identifier, "Expect variable name.")?
+            .clone();
+        self.consume_contextual("in", "Expect 'in' after loop variable.")?;
+        let iterable = self.expression()?;
+        self.consume(&TokenType::RightParen, "Expect ')' after for clauses.")?;
+
+        self.begin_scope();
+        self.declare(&name, false);
+        let body = self.statement()?;
+        self.end_scope();
+
+        Ok(Statement::ForIn {
+            name,
+            iterable,
+            body: Box::new(body),
+        })
+    }
+
     pub fn if_(&mut self) -> StatementParserResult {
         self.consume(&TokenType::LeftParen, "Expect '(' after 'if'.")?;
         let condition = self.expression()?;
@@ -180,12 +570,205 @@ impl Parser {
         })
     }
 
+    pub fn match_statement(&mut self) -> StatementParserResult {
+        let keyword = self.previous().clone();
+
+        self.consume(&TokenType::LeftParen, "Expect '(' after 'match'.")?;
+        let subject = self.expression()?;
+        self.consume(&TokenType::RightParen, "Expect ')' after match value.")?;
+        self.consume(&TokenType::LeftBrace, "Expect '{' before match arms.")?;
+
+        let mut arms: Vec<MatchArm> = Vec::new();
+        let mut exhausted = false;
+
+        while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
+            let start = self.peek().clone();
+            let pattern = self.pattern()?;
+
+            self.begin_scope();
+            for name in pattern.bindings() {
+                self.declare(name, false);
+            }
+
+            let mut guard: Option<Expression> = None;
+            if self.match_(&[&TokenType::If]) {
+                guard = Some(self.expression()?);
+            }
+
+            self.consume(&TokenType::FatArrow, "Expect '=>' after match pattern.")?;
+            let body = self.statement()?;
+            self.end_scope();
+
+            self.match_(&[&TokenType::Comma]);
+
+            if exhausted {
+                self.warning(&start, "Unreachable match arm.");
+            }
+
+            exhausted |= guard.is_none() && pattern.is_irrefutable();
+
+            arms.push(MatchArm {
+                pattern,
+                guard,
+                body,
+            });
+        }
+
+        self.consume(&TokenType::RightBrace, "Expect '}' after match arms.")?;
+
+        Ok(Statement::Match {
+            keyword,
+            subject,
+            arms,
+        })
+    }
+
+    pub fn pattern(&mut self) -> Result<Pattern, ParseError> {
+        let mut alternatives = vec![self.primary_pattern()?];
+
+        while self.match_(&[&TokenType::Pipe]) {
+            let pipe = self.previous().clone();
+            let alternative = self.primary_pattern()?;
+
+            if binding_names(&alternative) != binding_names(&alternatives[0]) {
+                return Err(self.error(
+                    &pipe,
+                    "All alternatives of a pattern must bind the same names.",
+                ));
+            }
+
+            alternatives.push(alternative);
+        }
+
+        if alternatives.len() == 1 {
+            return Ok(alternatives.remove(0));
+        }
+
+        Ok(Pattern::Alternative(alternatives))
+    }
+
+    pub fn primary_pattern(&mut self) -> Result<Pattern, ParseError> {
+        if self.match_(&[&TokenType::False]) {
+            return Ok(Pattern::Literal(Literal::Boolean(false)));
+        }
+
+        if self.match_(&[&TokenType::True]) {
+            return Ok(Pattern::Literal(Literal::Boolean(true)));
+        }
+
+        if self.match_(&[&TokenType::Nil]) {
+            return Ok(Pattern::Literal(Literal::Nil));
+        }
+
+        if self.match_(&[&TokenType::Number, &TokenType::String]) {
+            return Ok(Pattern::Literal(
+                self.previous().literal.as_ref().unwrap().clone(),
+            ));
+        }
+
+        if self.match_(&[&TokenType::Minus]) {
+            let number = self.consume(&TokenType::Number, "Expect number after '-'.")?;
+
+            let Some(Literal::Number(value)) = number.literal else {
+                panic!("unreachable");
+            };
+
+            return Ok(Pattern::Literal(Literal::Number(-value)));
+        }
+
+        if self.match_(&[&TokenType::Identifier]) {
+            let name = self.previous().clone();
+
+            if name.lexeme == "_" {
+                return Ok(Pattern::Wildcard);
+            }
+
+            if self.match_(&[&TokenType::Dot]) {
+                return self.variant_pattern(Expression::Variable(name));
+            }
+
+            return Ok(Pattern::Binding(name));
+        }
+
+        if self.match_(&[&TokenType::LeftBracket]) {
+            let mut elements: Vec<Pattern> = Vec::new();
+            let mut rest: Option<Token> = None;
+
+            while !self.check(&TokenType::RightBracket) {
+                if self.match_(&[&TokenType::DotDotDot]) {
+                    rest = Some(
+                        self.consume(&TokenType::Identifier, "Expect name after '...'.")?
+                            .clone(),
+                    );
+
+                    break;
+                }
+
+                elements.push(self.pattern()?);
+
+                if !self.match_(&[&TokenType::Comma]) {
+                    break;
+                }
+            }
+
+            self.consume(&TokenType::RightBracket, "Expect ']' after list pattern.")?;
+
+            return Ok(Pattern::List { elements, rest });
+        }
+
+        Err(self.error(self.peek(), "Expect pattern."))
+    }
+
+    /// Parses the rest of `State.Done(code)` or `module.State.Done`, where every name
+    /// before the last one leads to the enum.
+    pub fn variant_pattern(&mut self, mut enumeration: Expression) -> Result<Pattern, ParseError> {
+        let mut tag = self
+            .consume(&TokenType::Identifier, "Expect variant name after '.'.")?
+            .clone();
+
+        while self.match_(&[&TokenType::Dot]) {
+            enumeration = Expression::Get {
+                object: Box::new(enumeration),
+                name: tag,
+            };
+            tag = self
+                .consume(&TokenType::Identifier, "Expect variant name after '.'.")?
+                .clone();
+        }
+
+        let mut fields: Option<Vec<Pattern>> = None;
+        if self.match_(&[&TokenType::LeftParen]) {
+            let mut patterns: Vec<Pattern> = Vec::new();
+
+            while !self.check(&TokenType::RightParen) {
+                patterns.push(self.pattern()?);
+
+                if !self.match_(&[&TokenType::Comma]) {
+                    break;
+                }
+            }
+
+            self.consume(&TokenType::RightParen, "Expect ')' after variant fields.")?;
+            fields = Some(patterns);
+        }
+
+        Ok(Pattern::Variant {
+            enumeration,
+            tag,
+            fields,
+        })
+    }
+
     pub fn print(&mut self) -> StatementParserResult {
-        let expression = self.expression()?;
+        let mut expressions = vec![self.expression()?];
+
+        while self.match_(&[&TokenType::Comma]) {
+            expressions.push(self.expression()?);
+        }
 
         self.consume(&TokenType::Semicolon, "Expect ';' after value.")?;
 
-        Ok(Statement::Print(expression))
+        Ok(Statement::Print(expressions))
     }
 
     pub fn return_(&mut self) -> StatementParserResult {
@@ -193,6 +776,10 @@ impl Parser {
 
         let mut value: Option<Expression> = None;
         if !self.check(&TokenType::Semicolon) {
+            if self.initializers.last() == Some(&true) {
+                return Err(self.error(&keyword, "Can't return a value from an initializer."));
+            }
+
             value = Some(self.expression()?);
         }
 
@@ -201,6 +788,88 @@ impl Parser {
         Ok(Statement::Return { keyword, value })
     }
 
+    pub fn throw(&mut self) -> StatementParserResult {
+        let keyword = self.previous().clone();
+        let value = self.expression()?;
+
+        self.consume(&TokenType::Semicolon, "Expect ';' after thrown value.")?;
+
+        Ok(Statement::Throw { keyword, value })
+    }
+
+    pub fn assert(&mut self) -> StatementParserResult {
+        let keyword = self.previous().clone();
+        let condition = self.expression()?;
+
+        let mut message: Option<Expression> = None;
+        if self.match_(&[&TokenType::Comma]) {
+            message = Some(self.expression()?);
+        }
+
+        self.consume(&TokenType::Semicolon, "Expect ';' after assertion.")?;
+
+        Ok(Statement::Assert {
+            keyword,
+            condition,
+            message,
+        })
+    }
+
+    pub fn try_(&mut self) -> StatementParserResult {
+        self.consume(&TokenType::LeftBrace, "Expect '{' after 'try'.")?;
+        let body = self.block()?;
+
+        let mut catch: Option<Catch> = None;
+        if self.match_(&[&TokenType::Catch]) {
+            self.consume(&TokenType::LeftParen, "Expect '(' after 'catch'.")?;
+            let name = self
+                .consume(&TokenType::Identifier, "Expect exception variable name.")?
+                .clone();
+            self.consume(&TokenType::RightParen, "Expect ')' after catch variable.")?;
+            self.consume(&TokenType::LeftBrace, "Expect '{' before catch body.")?;
+
+            self.begin_scope();
+            self.declare(&name, false);
+            let body = self.block()?;
+            self.end_scope();
+
+            catch = Some(Catch { name, body });
+        }
+
+        let mut finally: Option<Vec<Statement>> = None;
+        if self.match_(&[&TokenType::Finally]) {
+            self.consume(&TokenType::LeftBrace, "Expect '{' after 'finally'.")?;
+            finally = Some(self.block()?);
+        }
+
+        if catch.is_none() && finally.is_none() {
+            return Err(self.error(self.peek(), "Expect 'catch' or 'finally' after try block."));
+        }
+
+        Ok(Statement::Try {
+            body,
+            catch,
+            finally,
+        })
+    }
+
+    pub fn yield_(&mut self) -> StatementParserResult {
+        let keyword = self.previous().clone();
+
+        if self.generators.last() != Some(&true) {
+            return Err(self.error(&keyword, "Can't yield outside a generator."));
+        }
+
+        let mut value: Option<Expression> = None;
+        if !self.check(&TokenType::Semicolon) {
+            value = Some(self.expression()?);
+        }
+
+        self.consume(&TokenType::Semicolon, "Expect ';' after yield value.")?;
+
+        Ok(Statement::Yield { keyword, value })
+    }
+
     pub fn while_(&mut self) -> StatementParserResult {
         self.consume(&TokenType::LeftParen, "Expect '(' after 'while'.")?;
         let condition = self.expression()?;
@@ -229,19 +898,48 @@ impl Parser {
             "Expect ';' after variable declaration.",
         )?;
 
+        self.declare(&name, false);
+
         Ok(Statement::Variable {
             name: name.clone(),
             initializer,
+            constant: false,
+        })
+    }
+
+    pub fn constant(&mut self) -> StatementParserResult {
+        let name = self
+            .consume(&TokenType::Identifier, "Expect constant name.")?
+            .clone();
+
+        self.consume(&TokenType::Equal, "Expect '=' after constant name.")?;
+        let initializer = self.expression()?;
+
+        self.consume(
+            &TokenType::Semicolon,
+            "Expect ';' after constant declaration.",
+        )?;
+
+        self.declare(&name, true);
+
+        Ok(Statement::Variable {
+            name,
+            initializer: Some(initializer),
+            constant: true,
         })
     }
 
     pub fn block(&mut self) -> Result<Vec<Statement>, ParseError> {
         let mut statements: Vec<Statement> = Vec::new();
 
+        self.begin_scope();
+
         while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
             statements.push(self.declaration()?);
         }
 
+        self.end_scope();
+
         self.consume(&TokenType::RightBrace, "Expect '}' after block.")?;
 
         Ok(statements)
@@ -260,22 +958,149 @@ impl Parser {
     }
 
     pub fn assignment(&mut self) -> ExpressionParserResult {
-        let expression = self.or()?;
+        let expression = self.conditional()?;
 
         if self.match_(&[&TokenType::Equal]) {
             let equals = self.previous().clone();
             let value = self.assignment()?;
 
-            if let Expression::Variable(name) = expression {
-                return Ok(Expression::Assign {
-                    name: name.clone(),
-                    right: Box::new(value),
-                });
+            match expression {
+                Expression::Variable(name) => {
+                    self.check_assignable(&name)?;
+
+                    return Ok(Expression::Assign {
+                        name: name.clone(),
+                        right: Box::new(value),
+                    });
+                }
+                Expression::Get { object, name } => {
+                    return Ok(Expression::Set {
+                        object,
+                        name,
+                        right: Box::new(value),
+                    });
+                }
+                Expression::Index {
+                    object,
+                    bracket,
+                    index,
+                } => {
+                    return Ok(Expression::SetIndex {
+                        object,
+                        bracket,
+                        index,
+                        right: Box::new(value),
+                    });
+                }
+                _ => {}
             }
 
             return Err(self.error(&equals, "Invalid assignment target."));
         }
 
+        if self.match_(&[
+            &TokenType::PlusEqual,
+            &TokenType::MinusEqual,
+            &TokenType::StarEqual,
+            &TokenType::SlashEqual,
+            &TokenType::PercentEqual,
+        ]) {
+            let compound = self.previous().clone();
+            let operator = self.compound_operator(&compound);
+            let value = self.assignment()?;
+
+            match expression {
+                Expression::Variable(name) => {
+                    self.check_assignable(&name)?;
+
+                    return Ok(Expression::Assign {
+                        name: name.clone(),
+                        right: Box::new(Expression::Binary {
+                            left: Box::new(Expression::Variable(name)),
+                            operator,
+                            right: Box::new(value),
+                        }),
+                    });
+                }
+                Expression::Get { object, name } => {
+                    return Ok(Expression::CompoundSet {
+                        object,
+                        name,
+                        operator,
+                        right: Box::new(value),
+                    });
+                }
+                Expression::Index {
+                    object,
+                    bracket,
+                    index,
+                } => {
+                    return Ok(Expression::CompoundSetIndex {
+                        object,
+                        bracket,
+                        index,
+                        operator,
+                        right: Box::new(value),
+                    });
+                }
+                _ => {}
+            }
+
+            return Err(self.error(&compound, "Invalid assignment target."));
+        }
+
+        Ok(expression)
+    }
+
+    /// Turns a compound assignment token such as `+=` into the binary operator it applies.
+    fn compound_operator(&self, token: &Token) -> Token {
+        let (token_type, lexeme) = match token.token_type {
+            TokenType::PlusEqual => (TokenType::Plus, "+"),
+            TokenType::MinusEqual => (TokenType::Minus, "-"),
+            TokenType::StarEqual => (TokenType::Star, "*"),
+            TokenType::SlashEqual => (TokenType::Slash, "/"),
+            TokenType::PercentEqual => (TokenType::Percent, "%"),
+            _ => panic!("unreachable"),
+        };
+
+        Token::new(token_type, lexeme.into(), None, token.line)
+    }
+
+    pub fn conditional(&mut self) -> ExpressionParserResult {
+        let condition = self.coalesce()?;
+
+        if self.match_(&[&TokenType::Question]) {
+            let then_branch = self.expression()?;
+            self.consume(
+                &TokenType::Colon,
+                "Expect ':' after then branch of conditional expression.",
+            )?;
+            let else_branch = self.conditional()?;
+
+            return Ok(Expression::Conditional {
+                condition: Box::new(condition),
+                then_branch: Box::new(then_branch),
+                else_branch: Box::new(else_branch),
+            });
+        }
+
+        Ok(condition)
+    }
+
+    pub fn coalesce(&mut self) -> ExpressionParserResult {
+        let mut expression = self.or()?;
+
+        while self.match_(&[&TokenType::QuestionQuestion]) {
+            let operator = self.previous().clone();
+            let right = self.or()?;
+
+            expression = Expression::Logical {
+                left: Box::new(expression),
+                operator,
+                right: Box::new(right),
+            };
+        }
+
         Ok(expression)
     }
 
@@ -331,7 +1156,7 @@ impl Parser {
     }
 
     pub fn comparison(&mut self) -> ExpressionParserResult {
-        let mut expression = self.term()?;
+        let mut expression = self.range()?;
 
         while self.match_(&[
             &TokenType::Greater,
@@ -339,6 +1164,91 @@ impl Parser {
             &TokenType::Less,
             &TokenType::LessEqual,
         ]) {
+            let operator = self.previous().clone();
+            let right = self.range()?;
+
+            expression = Expression::Binary {
+                left: Box::new(expression),
+                operator,
+                right: Box::new(right),
+            }
+        }
+
+        Ok(expression)
+    }
+
+    pub fn range(&mut self) -> ExpressionParserResult {
+        let expression = self.bitwise_or()?;
+
+        if self.match_(&[&TokenType::DotDot]) {
+            let operator = self.previous().clone();
+            let right = self.bitwise_or()?;
+
+            return Ok(Expression::Binary {
+                left: Box::new(expression),
+                operator,
+                right: Box::new(right),
+            });
+        }
+
+        Ok(expression)
+    }
+
+    pub fn bitwise_or(&mut self) -> ExpressionParserResult {
+        let mut expression = self.bitwise_xor()?;
+
+        while self.match_(&[&TokenType::Pipe]) {
+            let operator = self.previous().clone();
+            let right = self.bitwise_xor()?;
+
+            expression = Expression::Binary {
+                left: Box::new(expression),
+                operator,
+                right: Box::new(right),
+            }
+        }
+
+        Ok(expression)
+    }
+
+    pub fn bitwise_xor(&mut self) -> ExpressionParserResult {
+        let mut expression = self.bitwise_and()?;
+
+        while self.match_(&[&TokenType::Caret]) {
+            let operator = self.previous().clone();
+            let right = self.bitwise_and()?;
+
+            expression = Expression::Binary {
+                left: Box::new(expression),
+                operator,
+                right: Box::new(right),
+            }
+        }
+
+        Ok(expression)
+    }
+
+    pub fn bitwise_and(&mut self) -> ExpressionParserResult {
+        let mut expression = self.shift()?;
+
+        while self.match_(&[&TokenType::Ampersand]) {
+            let operator = self.previous().clone();
+            let right = self.shift()?;
+
+            expression = Expression::Binary {
+                left: Box::new(expression),
+                operator,
+                right: Box::new(right),
+            }
+        }
+
+        Ok(expression)
+    }
+
+    pub fn shift(&mut self) -> ExpressionParserResult {
+        let mut expression = self.term()?;
+
+        while self.match_(&[&TokenType::LessLess, &TokenType::GreaterGreater]) {
             let operator = self.previous().clone();
             let right = self.term()?;
 
@@ -372,7 +1282,7 @@ impl Parser {
     pub fn factor(&mut self) -> ExpressionParserResult {
         let mut expression = self.unary()?;
 
-        while self.match_(&[&TokenType::Slash, &TokenType::Star]) {
+        while self.match_(&[&TokenType::Slash, &TokenType::Star, &TokenType::Percent]) {
             let operator = self.previous().clone();
             let right = self.unary()?;
 
@@ -397,14 +1307,55 @@ impl Parser {
             });
         }
 
-        self.call()
+        self.exponent()
+    }
+
+    pub fn exponent(&mut self) -> ExpressionParserResult {
+        let expression = self.call()?;
+
+        if self.match_(&[&TokenType::StarStar]) {
+            let operator = self.previous().clone();
+            let right = self.unary()?;
+
+            return Ok(Expression::Binary {
+                left: Box::new(expression),
+                operator,
+                right: Box::new(right),
+            });
+        }
+
+        Ok(expression)
     }
 
     pub fn call(&mut self) -> ExpressionParserResult {
         let mut expression = self.primary()?;
 
-        while self.match_(&[&TokenType::LeftParen]) {
-            expression = self.finish_call(expression)?
+        loop {
+            if self.match_(&[&TokenType::LeftParen]) {
+                expression = self.finish_call(expression)?;
+            } else if self.match_(&[&TokenType::Dot]) {
+                let name = self
+                    .consume(&TokenType::Identifier, "Expect property name after '.'.")?
+                    .clone();
+
+                expression = Expression::Get {
+                    object: Box::new(expression),
+                    name,
+                };
+            } else if self.match_(&[&TokenType::LeftBracket]) {
+                let index = self.expression()?;
+                let bracket = self
+                    .consume(&TokenType::RightBracket, "Expect ']' after index.")?
+                    .clone();
+
+                expression = Expression::Index {
+                    object: Box::new(expression),
+                    bracket,
+                    index: Box::new(index),
+                };
+            } else {
+                break;
+            }
         }
 
         Ok(expression)
@@ -419,7 +1370,7 @@ impl Parser {
                     return Err(self.error(self.peek(), "Can't have more than 255 arguments."));
                 }
 
-                arguments.push(self.expression()?);
+                arguments.push(self.element()?);
 
                 if !self.match_(&[&TokenType::Comma]) {
                     break;
@@ -459,6 +1410,37 @@ impl Parser {
             return Ok(Expression::Variable(self.previous().clone()));
         }
 
+        if self.match_(&[&TokenType::This]) {
+            let keyword = self.previous().clone();
+
+            if self.classes.is_empty() {
+                return Err(self.error(&keyword, "Can't use 'this' outside of a class."));
+            }
+
+            return Ok(Expression::This(keyword));
+        }
+
+        if self.match_(&[&TokenType::Super]) {
+            let keyword = self.previous().clone();
+
+            match self.classes.last() {
+                None => return Err(self.error(&keyword, "Can't use 'super' outside of a class.")),
+                Some(false) => {
+                    return Err(
+                        self.error(&keyword, "Can't use 'super' in a class with no superclass.")
+                    )
+                }
+                Some(true) => {}
+            }
+
+            self.consume(&TokenType::Dot, "Expect '.' after 'super'.")?;
+            let method = self
+                .consume(&TokenType::Identifier, "Expect superclass method name.")?
+                .clone();
+
+            return Ok(Expression::Super { keyword, method });
+        }
+
         if self.match_(&[&TokenType::LeftParen]) {
             let expression = self.expression()?;
             self.consume(&TokenType::RightParen, "Expect ')' after expression.")?;
@@ -466,9 +1448,106 @@ impl Parser {
             return Ok(Expression::Grouping(Box::new(expression)));
         }
 
+        if self.match_(&[&TokenType::LeftBracket]) {
+            let mut elements: Vec<Expression> = Vec::new();
+
+            while !self.check(&TokenType::RightBracket) {
+                elements.push(self.element()?);
+
+                if !self.match_(&[&TokenType::Comma]) {
+                    break;
+                }
+            }
+
+            self.consume(&TokenType::RightBracket, "Expect ']' after list elements.")?;
+
+            return Ok(Expression::List(elements));
+        }
+
+        if self.match_(&[&TokenType::LeftBrace]) {
+            let mut entries: Vec<(String, Expression)> = Vec::new();
+
+            while !self.check(&TokenType::RightBrace) {
+                let key = match self.advance().clone() {
+                    Token {
+                        token_type: TokenType::Identifier,
+                        lexeme,
+                        ..
+                    } => lexeme,
+                    Token {
+                        literal: Some(Literal::String(value)),
+                        ..
+                    } => value.to_string(),
+                    token => return Err(self.error(&token, "Expect map key.")),
+                };
+
+                self.consume(&TokenType::Colon, "Expect ':' after map key.")?;
+                entries.push((key, self.expression()?));
+
+                if !self.match_(&[&TokenType::Comma]) {
+                    break;
+                }
+            }
+
+            self.consume(&TokenType::RightBrace, "Expect '}' after map entries.")?;
+
+            return Ok(Expression::Map(entries));
+        }
+
         Err(self.error(self.peek(), "Expect expression."))
     }
 
+    /// An element of an argument list or list literal, which may be spread with `...`.
+    pub fn element(&mut self) -> ExpressionParserResult {
+        if self.match_(&[&TokenType::DotDotDot]) {
+            let operator = self.previous().clone();
+            let right = self.expression()?;
+
+            return Ok(Expression::Spread {
+                operator,
+                right: Box::new(right),
+            });
+        }
+
+        self.expression()
+    }
+
+    pub fn begin_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    pub fn end_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    pub fn declare(&mut self, name: &Token, constant: bool) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.lexeme.clone(), constant);
+        }
+    }
+
+    /// Rejects assignments to a constant declared earlier in the same function. Bindings
+    /// from enclosing functions may be redeclared before the assignment runs, so those
+    /// are left to the interpreter.
+    pub fn check_assignable(&self, name: &Token) -> Result<(), ParseError> {
+        let start = self.functions.last().copied().unwrap_or(0);
+
+        for scope in self.scopes[start..].iter().rev() {
+            match scope.get(&name.lexeme) {
+                Some(true) => {
+                    return Err(self.error(
+                        name,
+                        &format!("Cannot assign to constant '{}'.", name.lexeme),
+                    ))
+                }
+                Some(false) => return Ok(()),
+                None => {}
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn match_(&mut self, token_types: &[&TokenType]) -> bool {
         for token_type in token_types {
             if self.check(token_type) {
@@ -488,6 +1567,23 @@ impl Parser {
         Err(self.error(self.peek(), message))
     }
 
+    /// Consumes an identifier used as a keyword only in this position, such as `from`.
+    pub fn consume_contextual(
+        &mut self,
+        lexeme: &str,
+        message: &str,
+    ) -> Result<&Token, ParseError> {
+        if self.check_contextual(lexeme) {
+            return Ok(self.advance());
+        }
+
+        Err(self.error(self.peek(), message))
+    }
+
+    pub fn check_contextual(&self, lexeme: &str) -> bool {
+        self.check(&TokenType::Identifier) && self.peek().lexeme == lexeme
+    }
+
     pub fn check(&self, token_type: &TokenType) -> bool {
         if self.is_at_end() {
             return false;
@@ -512,10 +1608,24 @@ impl Parser {
         &self.tokens[self.current]
     }
 
+    /// The token `n` positions ahead of the current one, or `Eof` past the end.
+    pub fn peek_at(&self, n: usize) -> &Token {
+        let index = (self.current + n).min(self.tokens.len() - 1);
+
+        &self.tokens[index]
+    }
+
     pub fn previous(&self) -> &Token {
         &self.tokens[self.current - 1]
     }
 
+    pub fn warning(&self, token: &Token, message: &str) {
+        eprintln!(
+            "[line {}] Warning at '{}': {message}",
+            token.line, token.lexeme
+        );
+    }
+
     pub fn error(&self, token: &Token, message: &str) -> ParseError {
         let error_message = if token.token_type == TokenType::Eof {
             format!("[line {}] Error at end: {message}", token.line)
@@ -526,6 +1636,14 @@ impl Parser {
             )
         };
 
-        return ParseError(error_message);
+        ParseError(error_message)
     }
 }
+
+fn binding_names(pattern: &Pattern) -> BTreeSet<&str> {
+    pattern
+        .bindings()
+        .into_iter()
+        .map(|name| name.lexeme.as_str())
+        .collect()
+}
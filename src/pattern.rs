@@ -0,0 +1,179 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    ErrorKind, Expression, Interpreter, InterpreterError, Literal, Statement, Token, Value,
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Pattern {
+    Literal(Literal),
+    /// `_`, which matches anything without binding it.
+    Wildcard,
+    Binding(Token),
+    Alternative(Vec<Pattern>),
+    /// `[a, b, ...rest]`, where a rest named `_` discards the remaining elements.
+    List {
+        elements: Vec<Pattern>,
+        rest: Option<Token>,
+    },
+    /// `State.Done(code)`, or `State.Done` to match the tag whatever the payload. The enum
+    /// may be reached through a module, as in `module.State.Done`.
+    Variant {
+        enumeration: Expression,
+        tag: Token,
+        fields: Option<Vec<Pattern>>,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub guard: Option<Expression>,
+    pub body: Statement,
+}
+
+impl Pattern {
+    /// Whether this pattern matches every value, making any later arm unreachable.
+    pub fn is_irrefutable(&self) -> bool {
+        match self {
+            Pattern::Wildcard | Pattern::Binding(_) => true,
+            Pattern::Alternative(patterns) => patterns.iter().any(Pattern::is_irrefutable),
+            _ => false,
+        }
+    }
+
+    /// The names this pattern binds when it matches.
+    pub fn bindings(&self) -> Vec<&Token> {
+        match self {
+            Pattern::Binding(name) => vec![name],
+            Pattern::Alternative(patterns) => patterns.iter().flat_map(Pattern::bindings).collect(),
+            Pattern::List { elements, rest } => elements
+                .iter()
+                .flat_map(Pattern::bindings)
+                .chain(rest.iter().filter(|rest| rest.lexeme != "_"))
+                .collect(),
+            Pattern::Variant { fields, .. } => fields
+                .iter()
+                .flatten()
+                .flat_map(Pattern::bindings)
+                .collect(),
+            Pattern::Literal(_) | Pattern::Wildcard => Vec::new(),
+        }
+    }
+
+    /// Tests `value` against this pattern, pushing the bound names onto `bindings` if it
+    /// matches. Variant patterns evaluate their enum, which fails if it isn't an enum or
+    /// lacks the tag.
+    pub fn matches(
+        &self,
+        value: &Value,
+        interpreter: &mut Interpreter,
+        bindings: &mut Vec<(String, Value)>,
+    ) -> Result<bool, InterpreterError> {
+        match self {
+            Pattern::Literal(literal) => Ok(Value::from(literal.clone()) == *value),
+            Pattern::Wildcard => Ok(true),
+            Pattern::Binding(name) => {
+                bindings.push((name.lexeme.clone(), value.clone()));
+                Ok(true)
+            }
+            Pattern::Alternative(patterns) => {
+                let length = bindings.len();
+
+                for pattern in patterns {
+                    bindings.truncate(length);
+
+                    if pattern.matches(value, interpreter, bindings)? {
+                        return Ok(true);
+                    }
+                }
+
+                Ok(false)
+            }
+            Pattern::List { elements, rest } => {
+                let Value::List(list) = value else {
+                    return Ok(false);
+                };
+
+                let list = list.borrow().clone();
+                let length_matches = match rest {
+                    Some(_) => list.len() >= elements.len(),
+                    None => list.len() == elements.len(),
+                };
+
+                if !length_matches || !all_match(elements, &list, interpreter, bindings)? {
+                    return Ok(false);
+                }
+
+                if let Some(rest) = rest.as_ref().filter(|rest| rest.lexeme != "_") {
+                    let remaining = list[elements.len()..].to_vec();
+                    bindings.push((
+                        rest.lexeme.clone(),
+                        Value::List(Rc::new(RefCell::new(remaining))),
+                    ));
+                }
+
+                Ok(true)
+            }
+            Pattern::Variant {
+                enumeration,
+                tag,
+                fields,
+            } => {
+                let Value::Enum(enumeration) = interpreter.evaluate(enumeration.clone())? else {
+                    return Err(InterpreterError {
+                        token: Some(tag.clone()),
+                        message: "Only enums have variants.".into(),
+                        kind: ErrorKind::Runtime,
+                    });
+                };
+
+                if !enumeration
+                    .variants
+                    .iter()
+                    .any(|(name, _)| *name == tag.lexeme)
+                {
+                    return Err(InterpreterError {
+                        token: Some(tag.clone()),
+                        message: format!(
+                            "Enum '{}' has no variant '{}'.",
+                            enumeration.name, tag.lexeme
+                        ),
+                        kind: ErrorKind::Runtime,
+                    });
+                }
+
+                let Value::Variant(variant) = value else {
+                    return Ok(false);
+                };
+
+                if !Rc::ptr_eq(&variant.enumeration, &enumeration) || variant.tag != tag.lexeme {
+                    return Ok(false);
+                }
+
+                match fields {
+                    Some(fields) => Ok(fields.len() == variant.values.len()
+                        && all_match(fields, &variant.values, interpreter, bindings)?),
+                    None => Ok(true),
+                }
+            }
+        }
+    }
+}
+
+/// Whether each pattern matches the value at the same position, stopping at the first that
+/// doesn't.
+fn all_match(
+    patterns: &[Pattern],
+    values: &[Value],
+    interpreter: &mut Interpreter,
+    bindings: &mut Vec<(String, Value)>,
+) -> Result<bool, InterpreterError> {
+    for (pattern, value) in patterns.iter().zip(values) {
+        if !pattern.matches(value, interpreter, bindings)? {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
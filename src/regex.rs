@@ -0,0 +1,791 @@
+/// A compiled regular expression, matched by backtracking over the characters of a string.
+///
+/// The pattern is compiled to a program for a matcher that keeps its backtracking state on
+/// the heap, so long texts can't overflow the stack, and that gives up after `STEP_LIMIT`
+/// steps from one starting position, so patterns like `(a*)*b` can't backtrack for ever.
+///
+/// Supports literals, `.`, character classes with ranges and negation, the `\d \w \s`
+/// shorthands and their negations, the `^ $ \b \B` anchors, capturing, named (`(?<name>...)`)
+/// and non-capturing (`(?:...)`) groups, alternation, and the `* + ? {n} {n,} {n,m}`
+/// quantifiers, each of which can be made lazy with a trailing `?`.
+#[derive(Debug)]
+pub struct Regex {
+    program: Vec<Instruction>,
+    /// The number of progress marks the program uses, one for each repetition.
+    marks: usize,
+    /// The number of groups, including the whole match as group 0.
+    pub groups: usize,
+    /// The names of named groups, with their indexes.
+    pub names: Vec<(String, usize)>,
+}
+
+/// The character range of each group in a match, or `None` for a group that took no part.
+pub type Captures = Vec<Option<(usize, usize)>>;
+
+/// How many steps the matcher takes from one starting position before giving up.
+const STEP_LIMIT: usize = 1_000_000;
+
+/// The largest count a `{n,m}` quantifier may have, since the repeated node is copied once
+/// for each count.
+const REPEAT_LIMIT: usize = 1_000;
+
+/// The most instructions a pattern may compile to.
+const PROGRAM_LIMIT: usize = 100_000;
+
+#[derive(Debug, Clone)]
+enum Node {
+    Character(char),
+    Any,
+    Class {
+        items: Vec<ClassItem>,
+        negated: bool,
+    },
+    Start,
+    End,
+    WordBoundary {
+        negated: bool,
+    },
+    Group {
+        index: Option<usize>,
+        alternatives: Vec<Vec<Node>>,
+    },
+    Repeat {
+        node: Box<Node>,
+        min: usize,
+        max: Option<usize>,
+        greedy: bool,
+    },
+}
+
+impl Node {
+    /// How many instructions the compiler emits for the node, counting a repeated node as at
+    /// least one so that the work of copying it is bounded too. Saturates rather than
+    /// overflowing, as nested repeats multiply.
+    fn size(&self) -> usize {
+        match self {
+            Node::Group {
+                index,
+                alternatives,
+            } => {
+                let saves = if index.is_some() { 2 } else { 0 };
+                let branches = 2 * (alternatives.len() - 1);
+
+                alternatives
+                    .iter()
+                    .flatten()
+                    .fold(saves + branches, |size, node| {
+                        size.saturating_add(node.size())
+                    })
+            }
+            Node::Repeat { node, min, max, .. } => {
+                let body = node.size().max(1);
+                let optional = match max {
+                    Some(max) => (max - min).saturating_mul(body.saturating_add(3)),
+                    None => body.saturating_add(4),
+                };
+
+                min.saturating_mul(body).saturating_add(optional)
+            }
+            _ => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum ClassItem {
+    Range(char, char),
+    Digit { negated: bool },
+    Word { negated: bool },
+    Space { negated: bool },
+}
+
+impl ClassItem {
+    fn matches(&self, character: char) -> bool {
+        match self {
+            ClassItem::Range(low, high) => (*low..=*high).contains(&character),
+            ClassItem::Digit { negated } => character.is_ascii_digit() != *negated,
+            ClassItem::Word { negated } => is_word(character) != *negated,
+            ClassItem::Space { negated } => character.is_whitespace() != *negated,
+        }
+    }
+}
+
+/// An escape inside a character class: a single character, or a shorthand like `\d`.
+enum ClassEscape {
+    Character(char),
+    Shorthand(ClassItem),
+}
+
+fn is_word(character: char) -> bool {
+    character.is_alphanumeric() || character == '_'
+}
+
+/// Why a pattern failed to compile, and the character position where it was noticed.
+#[derive(Debug, thiserror::Error)]
+#[error("{message} at position {position}")]
+pub struct RegexError {
+    pub message: String,
+    pub position: usize,
+}
+
+/// The matcher ran out of steps before deciding whether the pattern matches.
+#[derive(Debug, thiserror::Error)]
+#[error("Regex too complex")]
+pub struct TooComplex;
+
+impl Regex {
+    pub fn new(pattern: &str) -> Result<Self, RegexError> {
+        let mut parser = RegexParser {
+            characters: pattern.chars().collect(),
+            current: 0,
+            groups: 1,
+            names: Vec::new(),
+        };
+
+        let alternatives = parser.alternatives()?;
+
+        if parser.current < parser.characters.len() {
+            return Err(parser.error("Unmatched ')'"));
+        }
+
+        let root = Node::Group {
+            index: Some(0),
+            alternatives,
+        };
+
+        // Checked before compiling, as nested repeats can multiply the size past what fits
+        // in memory.
+        if root.size() >= PROGRAM_LIMIT {
+            return Err(parser.error("Pattern too large"));
+        }
+
+        let mut compiler = Compiler {
+            program: Vec::new(),
+            marks: 0,
+        };
+        compiler.node(&root);
+        compiler.program.push(Instruction::Match);
+
+        Ok(Regex {
+            program: compiler.program,
+            marks: compiler.marks,
+            groups: parser.groups,
+            names: parser.names,
+        })
+    }
+
+    /// Finds the leftmost match starting at or after the character position `start`.
+    pub fn find_at(&self, text: &[char], start: usize) -> Result<Option<Captures>, TooComplex> {
+        Matcher::new(self, text).find(start)
+    }
+
+    /// Every non-overlapping match, from left to right.
+    pub fn find_all(&self, text: &[char]) -> Result<Vec<Captures>, TooComplex> {
+        let mut matcher = Matcher::new(self, text);
+        let mut matches: Vec<Captures> = Vec::new();
+        let mut start = 0;
+
+        while let Some(captures) = matcher.find(start)? {
+            let Some((begin, end)) = captures[0] else {
+                break;
+            };
+
+            // After an empty match, the next one has to start at least a character later.
+            start = if end == begin { end + 1 } else { end };
+            matches.push(captures);
+
+            if start > text.len() {
+                break;
+            }
+        }
+
+        Ok(matches)
+    }
+}
+
+struct RegexParser {
+    characters: Vec<char>,
+    current: usize,
+    groups: usize,
+    names: Vec<(String, usize)>,
+}
+
+impl RegexParser {
+    fn alternatives(&mut self) -> Result<Vec<Vec<Node>>, RegexError> {
+        let mut alternatives = vec![self.sequence()?];
+
+        while self.match_('|') {
+            alternatives.push(self.sequence()?);
+        }
+
+        Ok(alternatives)
+    }
+
+    fn sequence(&mut self) -> Result<Vec<Node>, RegexError> {
+        let mut nodes: Vec<Node> = Vec::new();
+
+        while let Some(character) = self.peek() {
+            if character == '|' || character == ')' {
+                break;
+            }
+
+            let atom = self.atom()?;
+            nodes.push(self.quantifier(atom)?);
+        }
+
+        Ok(nodes)
+    }
+
+    fn atom(&mut self) -> Result<Node, RegexError> {
+        let Some(character) = self.advance() else {
+            return Err(self.error("Unexpected end of pattern"));
+        };
+
+        match character {
+            '(' => self.group(),
+            '[' => self.class(),
+            '.' => Ok(Node::Any),
+            '^' => Ok(Node::Start),
+            '$' => Ok(Node::End),
+            '\\' => self.escape(),
+            '*' | '+' | '?' => Err(self.error("Nothing to repeat")),
+            character => Ok(Node::Character(character)),
+        }
+    }
+
+    fn group(&mut self) -> Result<Node, RegexError> {
+        let mut index = None;
+
+        if self.match_('?') {
+            if self.match_('<') {
+                let mut name = String::new();
+                while let Some(character) = self.advance() {
+                    if character == '>' {
+                        break;
+                    }
+                    name.push(character);
+                }
+
+                if name.is_empty() || !name.chars().all(is_word) {
+                    return Err(self.error("Invalid group name"));
+                }
+
+                self.names.push((name, self.groups));
+                index = Some(self.groups);
+                self.groups += 1;
+            } else if !self.match_(':') {
+                return Err(self.error("Unknown group flag"));
+            }
+        } else {
+            index = Some(self.groups);
+            self.groups += 1;
+        }
+
+        let alternatives = self.alternatives()?;
+
+        if !self.match_(')') {
+            return Err(self.error("Missing ')'"));
+        }
+
+        Ok(Node::Group {
+            index,
+            alternatives,
+        })
+    }
+
+    fn class(&mut self) -> Result<Node, RegexError> {
+        let negated = self.match_('^');
+        let mut items: Vec<ClassItem> = Vec::new();
+
+        // A `]` right after the opening bracket is a literal.
+        if self.match_(']') {
+            items.push(ClassItem::Range(']', ']'));
+        }
+
+        loop {
+            let low = match self.advance() {
+                Some(']') => break,
+                Some('\\') => match self.class_escape()? {
+                    ClassEscape::Character(character) => character,
+                    ClassEscape::Shorthand(item) => {
+                        items.push(item);
+                        continue;
+                    }
+                },
+                Some(character) => character,
+                None => return Err(self.error("Missing ']'")),
+            };
+
+            let high = if self.peek() == Some('-') && self.peek_at(1).is_some_and(|c| c != ']') {
+                self.advance();
+
+                match self.advance() {
+                    Some('\\') => match self.class_escape()? {
+                        ClassEscape::Character(character) => character,
+                        ClassEscape::Shorthand(_) => {
+                            return Err(self.error("Invalid range in class"))
+                        }
+                    },
+                    Some(character) => character,
+                    None => return Err(self.error("Missing ']'")),
+                }
+            } else {
+                low
+            };
+
+            if high < low {
+                return Err(self.error("Invalid range in class"));
+            }
+
+            items.push(ClassItem::Range(low, high));
+        }
+
+        Ok(Node::Class { items, negated })
+    }
+
+    fn class_escape(&mut self) -> Result<ClassEscape, RegexError> {
+        let Some(character) = self.advance() else {
+            return Err(self.error("Unexpected end of pattern"));
+        };
+
+        Ok(match shorthand(character) {
+            Some(item) => ClassEscape::Shorthand(item),
+            None => ClassEscape::Character(unescape(character)),
+        })
+    }
+
+    fn escape(&mut self) -> Result<Node, RegexError> {
+        let Some(character) = self.advance() else {
+            return Err(self.error("Unexpected end of pattern"));
+        };
+
+        match character {
+            'b' => Ok(Node::WordBoundary { negated: false }),
+            'B' => Ok(Node::WordBoundary { negated: true }),
+            character => match shorthand(character) {
+                Some(item) => Ok(Node::Class {
+                    items: vec![item],
+                    negated: false,
+                }),
+                None => Ok(Node::Character(unescape(character))),
+            },
+        }
+    }
+
+    fn quantifier(&mut self, node: Node) -> Result<Node, RegexError> {
+        let (min, max) = if self.match_('*') {
+            (0, None)
+        } else if self.match_('+') {
+            (1, None)
+        } else if self.match_('?') {
+            (0, Some(1))
+        } else if let Some(counts) = self.counts() {
+            counts
+        } else {
+            return Ok(node);
+        };
+
+        if matches!(node, Node::Start | Node::End | Node::WordBoundary { .. }) {
+            return Err(self.error("Nothing to repeat"));
+        }
+
+        if max.is_some_and(|max| max < min) {
+            return Err(self.error("Invalid repetition count"));
+        }
+
+        if min.max(max.unwrap_or(0)) > REPEAT_LIMIT {
+            return Err(self.error("Repetition count too large"));
+        }
+
+        let greedy = !self.match_('?');
+
+        Ok(Node::Repeat {
+            node: Box::new(node),
+            min,
+            max,
+            greedy,
+        })
+    }
+
+    /// Parses `{n}`, `{n,}` or `{n,m}`. Anything else is left alone, so that a `{` that
+    /// doesn't start one is read as a literal.
+    fn counts(&mut self) -> Option<(usize, Option<usize>)> {
+        if self.peek() != Some('{') {
+            return None;
+        }
+
+        let rest: String = self.characters[self.current + 1..].iter().collect();
+        let end = rest.find('}')?;
+        let body = &rest[..end];
+
+        let number = |text: &str| -> Option<usize> {
+            match text.chars().all(|c| c.is_ascii_digit()) && !text.is_empty() {
+                true => text.parse().ok(),
+                false => None,
+            }
+        };
+
+        let counts = match body.split_once(',') {
+            None => {
+                let count = number(body)?;
+                (count, Some(count))
+            }
+            Some((min, "")) => (number(min)?, None),
+            Some((min, max)) => (number(min)?, Some(number(max)?)),
+        };
+
+        self.current += body.chars().count() + 2;
+        Some(counts)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.characters.get(self.current).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.characters.get(self.current + offset).copied()
+    }
+
+    fn match_(&mut self, expected: char) -> bool {
+        if self.peek() != Some(expected) {
+            return false;
+        }
+
+        self.current += 1;
+        true
+    }
+
+    fn advance(&mut self) -> Option<char> {
+        let character = self.peek()?;
+        self.current += 1;
+
+        Some(character)
+    }
+
+    fn error(&self, message: &str) -> RegexError {
+        RegexError {
+            message: message.into(),
+            position: self.current,
+        }
+    }
+}
+
+fn shorthand(character: char) -> Option<ClassItem> {
+    match character {
+        'd' => Some(ClassItem::Digit { negated: false }),
+        'D' => Some(ClassItem::Digit { negated: true }),
+        'w' => Some(ClassItem::Word { negated: false }),
+        'W' => Some(ClassItem::Word { negated: true }),
+        's' => Some(ClassItem::Space { negated: false }),
+        'S' => Some(ClassItem::Space { negated: true }),
+        _ => None,
+    }
+}
+
+fn unescape(character: char) -> char {
+    match character {
+        'n' => '\n',
+        'r' => '\r',
+        't' => '\t',
+        character => character,
+    }
+}
+
+#[derive(Debug)]
+enum Instruction {
+    Character(char),
+    Any,
+    Class {
+        items: Vec<ClassItem>,
+        negated: bool,
+    },
+    Start,
+    End,
+    WordBoundary {
+        negated: bool,
+    },
+    /// Records the position in a capture slot: `2 * group` for the start of a group and
+    /// `2 * group + 1` for its end.
+    Save(usize),
+    /// Continues at the first target, backtracking to the second.
+    Split(usize, usize),
+    Jump(usize),
+    /// Records the position where an iteration of a repetition starts.
+    Mark(usize),
+    /// Fails an iteration that consumed nothing since its `Mark`, which would otherwise
+    /// repeat for ever.
+    Progress(usize),
+    Match,
+}
+
+struct Compiler {
+    program: Vec<Instruction>,
+    marks: usize,
+}
+
+impl Compiler {
+    fn emit(&mut self, instruction: Instruction) -> usize {
+        self.program.push(instruction);
+        self.program.len() - 1
+    }
+
+    fn node(&mut self, node: &Node) {
+        match node {
+            Node::Character(character) => {
+                self.emit(Instruction::Character(*character));
+            }
+            Node::Any => {
+                self.emit(Instruction::Any);
+            }
+            Node::Class { items, negated } => {
+                self.emit(Instruction::Class {
+                    items: items.clone(),
+                    negated: *negated,
+                });
+            }
+            Node::Start => {
+                self.emit(Instruction::Start);
+            }
+            Node::End => {
+                self.emit(Instruction::End);
+            }
+            Node::WordBoundary { negated } => {
+                self.emit(Instruction::WordBoundary { negated: *negated });
+            }
+            Node::Group {
+                index,
+                alternatives,
+            } => {
+                if let Some(index) = index {
+                    self.emit(Instruction::Save(2 * index));
+                }
+
+                self.alternatives(alternatives);
+
+                if let Some(index) = index {
+                    self.emit(Instruction::Save(2 * index + 1));
+                }
+            }
+            Node::Repeat {
+                node,
+                min,
+                max,
+                greedy,
+            } => self.repeat(node, *min, *max, *greedy),
+        }
+    }
+
+    /// Tries each alternative in turn, jumping past the others once one has matched.
+    fn alternatives(&mut self, alternatives: &[Vec<Node>]) {
+        let mut jumps: Vec<usize> = Vec::new();
+
+        for (index, alternative) in alternatives.iter().enumerate() {
+            let last = index + 1 == alternatives.len();
+            let split = (!last).then(|| self.emit(Instruction::Split(0, 0)));
+
+            for node in alternative {
+                self.node(node);
+            }
+
+            if let Some(split) = split {
+                jumps.push(self.emit(Instruction::Jump(0)));
+                self.program[split] = Instruction::Split(split + 1, self.program.len());
+            }
+        }
+
+        for jump in jumps {
+            self.program[jump] = Instruction::Jump(self.program.len());
+        }
+    }
+
+    /// Copies the node for each required iteration, then adds the optional ones: up to the
+    /// maximum count, or a loop without one. A greedy repetition tries another iteration
+    /// before the rest of the pattern, a lazy one after.
+    fn repeat(&mut self, node: &Node, min: usize, max: Option<usize>, greedy: bool) {
+        for _ in 0..min {
+            self.node(node);
+        }
+
+        let mark = self.marks;
+        self.marks += 1;
+
+        let branch = |body: usize, skip: usize| match greedy {
+            true => Instruction::Split(body, skip),
+            false => Instruction::Split(skip, body),
+        };
+
+        match max {
+            Some(max) => {
+                let mut splits: Vec<usize> = Vec::new();
+
+                for _ in min..max {
+                    splits.push(self.emit(Instruction::Split(0, 0)));
+                    self.emit(Instruction::Mark(mark));
+                    self.node(node);
+                    self.emit(Instruction::Progress(mark));
+                }
+
+                for split in splits {
+                    self.program[split] = branch(split + 1, self.program.len());
+                }
+            }
+            None => {
+                let split = self.emit(Instruction::Split(0, 0));
+                self.emit(Instruction::Mark(mark));
+                self.node(node);
+                self.emit(Instruction::Progress(mark));
+                self.emit(Instruction::Jump(split));
+
+                self.program[split] = branch(split + 1, self.program.len());
+            }
+        }
+    }
+}
+
+/// Undoes part of the matcher's state when a path through the program fails.
+enum Backtrack {
+    /// Tries another path from an earlier `Split`.
+    Retry {
+        pc: usize,
+        position: usize,
+    },
+    Slot {
+        slot: usize,
+        value: Option<usize>,
+    },
+    Mark {
+        mark: usize,
+        value: usize,
+    },
+}
+
+struct Matcher<'a> {
+    regex: &'a Regex,
+    text: &'a [char],
+    slots: Vec<Option<usize>>,
+    marks: Vec<usize>,
+    stack: Vec<Backtrack>,
+}
+
+impl<'a> Matcher<'a> {
+    fn new(regex: &'a Regex, text: &'a [char]) -> Self {
+        Matcher {
+            regex,
+            text,
+            slots: vec![None; 2 * regex.groups],
+            marks: vec![0; regex.marks],
+            stack: Vec::new(),
+        }
+    }
+
+    fn find(&mut self, start: usize) -> Result<Option<Captures>, TooComplex> {
+        for position in start..=self.text.len() {
+            if self.run(position)? {
+                let captures = self
+                    .slots
+                    .chunks(2)
+                    .map(|slots| slots[0].zip(slots[1]))
+                    .collect();
+
+                return Ok(Some(captures));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Runs the program from `position`, leaving the groups of a match in `slots`.
+    fn run(&mut self, mut position: usize) -> Result<bool, TooComplex> {
+        let program = &self.regex.program;
+
+        self.slots.fill(None);
+        self.stack.clear();
+        let mut pc = 0;
+
+        for _ in 0..STEP_LIMIT {
+            let next = match &program[pc] {
+                Instruction::Match => return Ok(true),
+                Instruction::Split(first, second) => {
+                    self.stack.push(Backtrack::Retry {
+                        pc: *second,
+                        position,
+                    });
+                    pc = *first;
+                    continue;
+                }
+                Instruction::Jump(target) => {
+                    pc = *target;
+                    continue;
+                }
+                Instruction::Save(slot) => {
+                    self.stack.push(Backtrack::Slot {
+                        slot: *slot,
+                        value: self.slots[*slot],
+                    });
+                    self.slots[*slot] = Some(position);
+                    Some(position)
+                }
+                Instruction::Mark(mark) => {
+                    self.stack.push(Backtrack::Mark {
+                        mark: *mark,
+                        value: self.marks[*mark],
+                    });
+                    self.marks[*mark] = position;
+                    Some(position)
+                }
+                Instruction::Progress(mark) => (position != self.marks[*mark]).then_some(position),
+                instruction => self.single(instruction, position),
+            };
+
+            match next {
+                Some(next) => {
+                    position = next;
+                    pc += 1;
+                }
+                None => match self.backtrack() {
+                    Some((retry, from)) => (pc, position) = (retry, from),
+                    None => return Ok(false),
+                },
+            }
+        }
+
+        Err(TooComplex)
+    }
+
+    /// Undoes the state changed since the latest `Split`, returning where to try next.
+    fn backtrack(&mut self) -> Option<(usize, usize)> {
+        while let Some(entry) = self.stack.pop() {
+            match entry {
+                Backtrack::Retry { pc, position } => return Some((pc, position)),
+                Backtrack::Slot { slot, value } => self.slots[slot] = value,
+                Backtrack::Mark { mark, value } => self.marks[mark] = value,
+            }
+        }
+
+        None
+    }
+
+    /// Matches an instruction that tests the text, returning the position after it.
+    fn single(&self, instruction: &Instruction, position: usize) -> Option<usize> {
+        let character = self.text.get(position).copied();
+
+        let matched = match instruction {
+            Instruction::Character(expected) => character == Some(*expected),
+            Instruction::Any => character.is_some_and(|character| character != '\n'),
+            Instruction::Class { items, negated } => character.is_some_and(|character| {
+                items.iter().any(|item| item.matches(character)) != *negated
+            }),
+            Instruction::Start => return (position == 0).then_some(position),
+            Instruction::End => return (position == self.text.len()).then_some(position),
+            Instruction::WordBoundary { negated } => {
+                let before = position > 0 && is_word(self.text[position - 1]);
+                let after = character.is_some_and(is_word);
+
+                return ((before != after) != *negated).then_some(position);
+            }
+            _ => unreachable!(),
+        };
+
+        matched.then_some(position + 1)
+    }
+}
@@ -26,21 +26,31 @@ impl Scanner {
             had_error: false,
             keywords: HashMap::from([
                 ("and", TokenType::And),
+                ("assert", TokenType::Assert),
+                ("catch", TokenType::Catch),
                 ("class", TokenType::Class),
+                ("const", TokenType::Const),
                 ("else", TokenType::Else),
+                ("enum", TokenType::Enum),
                 ("false", TokenType::False),
+                ("finally", TokenType::Finally),
                 ("for", TokenType::For),
                 ("fun", TokenType::Fun),
                 ("if", TokenType::If),
+                ("import", TokenType::Import),
+                ("match", TokenType::Match),
                 ("nil", TokenType::Nil),
                 ("or", TokenType::Or),
                 ("print", TokenType::Print),
                 ("return", TokenType::Return),
                 ("super", TokenType::Super),
                 ("this", TokenType::This),
+                ("throw", TokenType::Throw),
                 ("true", TokenType::True),
+                ("try", TokenType::Try),
                 ("var", TokenType::Var),
                 ("while", TokenType::While),
+                ("yield", TokenType::Yield),
             ]),
         }
     }
@@ -77,21 +87,44 @@ impl Scanner {
             ')' => self.add_token(TokenType::RightParen, None),
             '{' => self.add_token(TokenType::LeftBrace, None),
             '}' => self.add_token(TokenType::RightBrace, None),
+            '[' => self.add_token(TokenType::LeftBracket, None),
+            ']' => self.add_token(TokenType::RightBracket, None),
             ',' => self.add_token(TokenType::Comma, None),
+            '.' if self.peek() == '.' && self.peek_at(1) == '.' => {
+                self.current += 2;
+                self.add_token(TokenType::DotDotDot, None)
+            }
+            '.' if self.match_('.') => self.add_token(TokenType::DotDot, None),
             '.' => self.add_token(TokenType::Dot, None),
+            '-' if self.match_('=') => self.add_token(TokenType::MinusEqual, None),
             '-' => self.add_token(TokenType::Minus, None),
+            '+' if self.match_('=') => self.add_token(TokenType::PlusEqual, None),
             '+' => self.add_token(TokenType::Plus, None),
             ';' => self.add_token(TokenType::Semicolon, None),
+            '*' if self.match_('*') => self.add_token(TokenType::StarStar, None),
+            '*' if self.match_('=') => self.add_token(TokenType::StarEqual, None),
             '*' => self.add_token(TokenType::Star, None),
+            '%' if self.match_('=') => self.add_token(TokenType::PercentEqual, None),
+            '%' => self.add_token(TokenType::Percent, None),
+            '&' => self.add_token(TokenType::Ampersand, None),
+            '|' => self.add_token(TokenType::Pipe, None),
+            '^' => self.add_token(TokenType::Caret, None),
+            ':' => self.add_token(TokenType::Colon, None),
+            '?' if self.match_('?') => self.add_token(TokenType::QuestionQuestion, None),
+            '?' => self.add_token(TokenType::Question, None),
             '=' if self.match_('=') => self.add_token(TokenType::EqualEqual, None),
+            '=' if self.match_('>') => self.add_token(TokenType::FatArrow, None),
             '=' => self.add_token(TokenType::Equal, None),
             '!' if self.match_('=') => self.add_token(TokenType::BangEqual, None),
             '!' => self.add_token(TokenType::Bang, None),
             '<' if self.match_('=') => self.add_token(TokenType::LessEqual, None),
+            '<' if self.match_('<') => self.add_token(TokenType::LessLess, None),
             '<' => self.add_token(TokenType::Less, None),
             '>' if self.match_('=') => self.add_token(TokenType::GreaterEqual, None),
+            '>' if self.match_('>') => self.add_token(TokenType::GreaterGreater, None),
             '>' => self.add_token(TokenType::Greater, None),
             '/' if self.match_('/') => self.advance_next_line(),
+            '/' if self.match_('=') => self.add_token(TokenType::SlashEqual, None),
             '/' => self.add_token(TokenType::Slash, None),
             ' ' | '\r' | '\t' => (),
             '\n' => self.line += 1,
@@ -212,15 +245,15 @@ impl Scanner {
     }
 
     fn is_number(&self, character: char) -> bool {
-        return character.is_numeric();
+        character.is_ascii_digit()
     }
 
     fn is_alpha(&self, character: char) -> bool {
-        return character.is_alphabetic() || character == '_';
+        character.is_alphabetic() || character == '_'
     }
 
     fn is_alpha_or_number(&self, character: char) -> bool {
-        return self.is_alpha(character) || self.is_number(character);
+        self.is_alpha(character) || self.is_number(character)
     }
 
     fn error(&mut self, line: usize, message: String) {
@@ -232,3 +265,28 @@ impl Scanner {
         self.had_error = true;
     }
 }
+
+/// Parses `text` with the scanner's rules for a number literal: digits, optionally followed
+/// by a `.` and more digits. The scanner makes a leading `-` a separate token, so it is
+/// stripped here and applied to the result.
+pub fn parse_number(text: &str) -> Option<f64> {
+    let (sign, digits) = match text.strip_prefix('-') {
+        Some(digits) => (-1.0, digits),
+        None => (1.0, text),
+    };
+
+    let mut scanner = Scanner::new(digits.into());
+    if !scanner.is_number(scanner.peek()) {
+        return None;
+    }
+
+    scanner.number();
+    if !scanner.is_at_end() {
+        return None;
+    }
+
+    match scanner.tokens.pop()?.literal {
+        Some(Literal::Number(value)) => Some(sign * value),
+        _ => None,
+    }
+}
@@ -1,5 +1,5 @@
 use {
-    crate::{Expression, Token},
+    crate::{Expression, MatchArm, Token},
     std::vec::Vec,
 };
 
@@ -8,18 +8,22 @@ pub enum Statement {
     Expression(Expression),
     Function {
         name: Token,
-        parameters: Vec<Token>,
+        parameters: Vec<Parameter>,
+        rest: Option<Token>,
         body: Vec<Statement>,
+        generator: bool,
     },
     If {
         condition: Expression,
         then_branch: Box<Statement>,
         else_branch: Option<Box<Statement>>,
     },
-    Print(Expression),
+    /// `print a, b;`, which writes the values separated by spaces.
+    Print(Vec<Expression>),
     Variable {
         name: Token,
         initializer: Option<Expression>,
+        constant: bool,
     },
     Return {
         keyword: Token,
@@ -29,5 +33,125 @@ pub enum Statement {
         condition: Expression,
         body: Box<Statement>,
     },
+    ForIn {
+        name: Token,
+        iterable: Expression,
+        body: Box<Statement>,
+    },
     Block(Vec<Statement>),
+    Throw {
+        keyword: Token,
+        value: Expression,
+    },
+    Assert {
+        keyword: Token,
+        condition: Expression,
+        message: Option<Expression>,
+    },
+    Try {
+        body: Vec<Statement>,
+        catch: Option<Catch>,
+        finally: Option<Vec<Statement>>,
+    },
+    Import {
+        keyword: Token,
+        path: Token,
+        alias: Option<Token>,
+        names: Vec<Token>,
+    },
+    Yield {
+        keyword: Token,
+        value: Option<Expression>,
+    },
+    Match {
+        keyword: Token,
+        subject: Expression,
+        arms: Vec<MatchArm>,
+    },
+    Enum {
+        name: Token,
+        variants: Vec<Variant>,
+    },
+    Class {
+        name: Token,
+        superclass: Option<Expression>,
+        traits: Vec<Expression>,
+        methods: Vec<Method>,
+    },
+    Trait {
+        name: Token,
+        methods: Vec<Method>,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum MethodKind {
+    Method,
+    /// `area { ... }`, called when the property is read.
+    Getter,
+    /// `set radius(value) { ... }`, called when the property is assigned.
+    Setter,
+    /// `class square(x) { ... }`, called on the class itself.
+    Static,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Method {
+    pub kind: MethodKind,
+    /// A `Statement::Function`.
+    pub function: Statement,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Variant {
+    pub name: Token,
+    pub fields: Vec<Token>,
+}
+
+impl Statement {
+    /// Whether a `yield` of the enclosing generator appears inside this statement.
+    pub fn contains_yield(&self) -> bool {
+        match self {
+            Statement::Yield { .. } => true,
+            Statement::Block(statements) => statements.iter().any(Statement::contains_yield),
+            Statement::If {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                then_branch.contains_yield()
+                    || else_branch
+                        .as_ref()
+                        .is_some_and(|branch| branch.contains_yield())
+            }
+            Statement::While { body, .. } | Statement::ForIn { body, .. } => body.contains_yield(),
+            Statement::Match { arms, .. } => arms.iter().any(|arm| arm.body.contains_yield()),
+            Statement::Try {
+                body,
+                catch,
+                finally,
+            } => {
+                body.iter().any(Statement::contains_yield)
+                    || catch
+                        .as_ref()
+                        .is_some_and(|catch| catch.body.iter().any(Statement::contains_yield))
+                    || finally
+                        .as_ref()
+                        .is_some_and(|finally| finally.iter().any(Statement::contains_yield))
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Parameter {
+    pub name: Token,
+    pub default: Option<Expression>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Catch {
+    pub name: Token,
+    pub body: Vec<Statement>,
 }
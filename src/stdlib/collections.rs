@@ -0,0 +1,356 @@
+use std::{cell::RefCell, cmp::Ordering, rc::Rc};
+
+use crate::{
+    native::NativeFunction, ErrorKind, EvaluateInterpreterResult, Interpreter, InterpreterError,
+    LoxIterator, Range, Token, Value,
+};
+
+use super::{argument_error, expect_function, expect_number};
+
+pub const FUNCTIONS: &[NativeFunction] = &[
+    NativeFunction {
+        name: "map",
+        arity: 2,
+        max_arity: Some(2),
+        function: map,
+    },
+    NativeFunction {
+        name: "filter",
+        arity: 2,
+        max_arity: Some(2),
+        function: filter,
+    },
+    NativeFunction {
+        name: "reduce",
+        arity: 2,
+        max_arity: Some(3),
+        function: reduce,
+    },
+    NativeFunction {
+        name: "sort",
+        arity: 1,
+        max_arity: Some(2),
+        function: sort,
+    },
+    NativeFunction {
+        name: "any",
+        arity: 1,
+        max_arity: Some(2),
+        function: any,
+    },
+    NativeFunction {
+        name: "all",
+        arity: 1,
+        max_arity: Some(2),
+        function: all,
+    },
+    NativeFunction {
+        name: "zip",
+        arity: 1,
+        max_arity: None,
+        function: zip,
+    },
+    NativeFunction {
+        name: "enumerate",
+        arity: 1,
+        max_arity: Some(1),
+        function: enumerate,
+    },
+    NativeFunction {
+        name: "range",
+        arity: 1,
+        max_arity: Some(3),
+        function: range,
+    },
+];
+
+fn list(values: Vec<Value>) -> Value {
+    Value::List(Rc::new(RefCell::new(values)))
+}
+
+/// The values a `for (var x in ...)` loop over `iterable` would visit.
+fn collect(
+    interpreter: &mut Interpreter,
+    iterable: &Value,
+    token: &Token,
+) -> Result<Vec<Value>, InterpreterError> {
+    let mut iterator = LoxIterator::new(iterable.clone(), interpreter, token)?;
+    let mut values: Vec<Value> = Vec::new();
+
+    while let Some(value) = iterator.next(interpreter, token)? {
+        values.push(value);
+    }
+
+    Ok(values)
+}
+
+/// Calls `function` back with `arguments`, reporting its arity errors at the native's call.
+fn call(
+    interpreter: &mut Interpreter,
+    function: &Value,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    interpreter.call_value(function.clone(), arguments, token.clone())
+}
+
+fn map(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    expect_function("map", &arguments[1], token)?;
+
+    let mut results: Vec<Value> = Vec::new();
+    for value in collect(interpreter, &arguments[0], token)? {
+        results.push(call(interpreter, &arguments[1], vec![value], token)?);
+    }
+
+    Ok(list(results))
+}
+
+fn filter(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    expect_function("filter", &arguments[1], token)?;
+
+    let mut results: Vec<Value> = Vec::new();
+    for value in collect(interpreter, &arguments[0], token)? {
+        let keep = call(interpreter, &arguments[1], vec![value.clone()], token)?;
+
+        if interpreter.is_truthy(keep) {
+            results.push(value);
+        }
+    }
+
+    Ok(list(results))
+}
+
+/// Folds the values from left to right, starting from `initial` or else the first value.
+fn reduce(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    expect_function("reduce", &arguments[1], token)?;
+
+    let mut values = collect(interpreter, &arguments[0], token)?.into_iter();
+    let Some(mut accumulator) = arguments.get(2).cloned().or_else(|| values.next()) else {
+        return Err(InterpreterError {
+            token: Some(token.clone()),
+            message: "Can't reduce an empty sequence without an initial value.".into(),
+            kind: ErrorKind::Runtime,
+        });
+    };
+
+    for value in values {
+        accumulator = call(interpreter, &arguments[1], vec![accumulator, value], token)?;
+    }
+
+    Ok(accumulator)
+}
+
+/// A sorted copy of the values. The optional comparator returns a negative number, zero or
+/// a positive number when its first argument goes before, with or after its second; without
+/// one, numbers and strings are sorted in ascending order. Equal values keep their order.
+fn sort(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    if let Some(comparator) = arguments.get(1) {
+        expect_function("sort", comparator, token)?;
+    }
+
+    let values = collect(interpreter, &arguments[0], token)?;
+
+    let mut compare = |a: &Value, b: &Value| -> Result<Ordering, InterpreterError> {
+        let Some(comparator) = arguments.get(1) else {
+            return match (a, b) {
+                (Value::Number(a), Value::Number(b)) => Ok(a.total_cmp(b)),
+                (Value::String(a), Value::String(b)) => Ok(a.cmp(b)),
+                _ => Err(InterpreterError {
+                    token: Some(token.clone()),
+                    message: "Can only sort numbers or strings without a comparator.".into(),
+                    kind: ErrorKind::Runtime,
+                }),
+            };
+        };
+
+        match call(interpreter, comparator, vec![a.clone(), b.clone()], token)? {
+            Value::Number(order) => Ok(order.partial_cmp(&0.0).unwrap_or(Ordering::Equal)),
+            _ => Err(InterpreterError {
+                token: Some(token.clone()),
+                message: "Comparator must return a number.".into(),
+                kind: ErrorKind::Runtime,
+            }),
+        }
+    };
+
+    Ok(list(merge_sort(values, &mut compare)?))
+}
+
+/// A stable merge sort whose comparisons can fail, unlike the standard library's sorts,
+/// which also may panic when a comparator isn't a total order.
+fn merge_sort(
+    mut values: Vec<Value>,
+    compare: &mut dyn FnMut(&Value, &Value) -> Result<Ordering, InterpreterError>,
+) -> Result<Vec<Value>, InterpreterError> {
+    if values.len() <= 1 {
+        return Ok(values);
+    }
+
+    let right = values.split_off(values.len() / 2);
+    let left = merge_sort(values, compare)?;
+    let right = merge_sort(right, compare)?;
+
+    let mut merged: Vec<Value> = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+
+    while let (Some(a), Some(b)) = (left.peek(), right.peek()) {
+        // Taking from the left on ties keeps the sort stable.
+        if compare(b, a)? == Ordering::Less {
+            merged.extend(right.next());
+        } else {
+            merged.extend(left.next());
+        }
+    }
+
+    merged.extend(left);
+    merged.extend(right);
+
+    Ok(merged)
+}
+
+/// Whether the predicate, or else the value itself, is truthy for some value, stopping at the
+/// first one that is.
+fn any(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    if let Some(predicate) = arguments.get(1) {
+        expect_function("any", predicate, token)?;
+    }
+
+    let mut iterator = LoxIterator::new(arguments[0].clone(), interpreter, token)?;
+    while let Some(value) = iterator.next(interpreter, token)? {
+        let result = match arguments.get(1) {
+            Some(predicate) => call(interpreter, predicate, vec![value], token)?,
+            None => value,
+        };
+
+        if interpreter.is_truthy(result) {
+            return Ok(Value::Boolean(true));
+        }
+    }
+
+    Ok(Value::Boolean(false))
+}
+
+/// Whether the predicate, or else the value itself, is truthy for every value, stopping at the
+/// first one that isn't.
+fn all(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    if let Some(predicate) = arguments.get(1) {
+        expect_function("all", predicate, token)?;
+    }
+
+    let mut iterator = LoxIterator::new(arguments[0].clone(), interpreter, token)?;
+    while let Some(value) = iterator.next(interpreter, token)? {
+        let result = match arguments.get(1) {
+            Some(predicate) => call(interpreter, predicate, vec![value], token)?,
+            None => value,
+        };
+
+        if !interpreter.is_truthy(result) {
+            return Ok(Value::Boolean(false));
+        }
+    }
+
+    Ok(Value::Boolean(true))
+}
+
+/// Lists of the values at the same position in each sequence, as long as the shortest. The
+/// sequences are advanced together, so none is read past the end of the shortest.
+fn zip(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let mut iterators: Vec<LoxIterator> = Vec::new();
+    for argument in &arguments {
+        iterators.push(LoxIterator::new(argument.clone(), interpreter, token)?);
+    }
+
+    let mut tuples: Vec<Value> = Vec::new();
+    loop {
+        let mut tuple: Vec<Value> = Vec::new();
+        for iterator in &mut iterators {
+            match iterator.next(interpreter, token)? {
+                Some(value) => tuple.push(value),
+                None => return Ok(list(tuples)),
+            }
+        }
+
+        tuples.push(list(tuple));
+    }
+}
+
+/// `[index, value]` pairs for each value.
+fn enumerate(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let pairs = collect(interpreter, &arguments[0], token)?
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| list(vec![Value::Number(index as f64), value]))
+        .collect();
+
+    Ok(list(pairs))
+}
+
+/// `range(end)`, `range(start, end)` or `range(start, end, step)`.
+fn range(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    let mut numbers: Vec<f64> = Vec::new();
+    for argument in &arguments {
+        numbers.push(expect_number("range", argument, token)?);
+    }
+
+    let range = match numbers.as_slice() {
+        [end] => Range {
+            start: 0.0,
+            end: *end,
+            step: 1.0,
+        },
+        [start, end] => Range {
+            start: *start,
+            end: *end,
+            step: 1.0,
+        },
+        [start, end, step] => Range {
+            start: *start,
+            end: *end,
+            step: *step,
+        },
+        _ => unreachable!(),
+    };
+
+    if range.start.is_nan() || range.end.is_nan() {
+        return Err(argument_error("range", "a bound other than NaN", token));
+    }
+
+    if range.step == 0.0 || range.step.is_nan() {
+        return Err(argument_error("range", "a non-zero step", token));
+    }
+
+    Ok(Value::Range(range))
+}
@@ -0,0 +1,221 @@
+use std::{iter::Peekable, str::Chars};
+
+use crate::{
+    native::NativeFunction, ErrorKind, EvaluateInterpreterResult, Interpreter, InterpreterError,
+    Token, Value,
+};
+
+use super::{expect_string, string};
+
+pub const FUNCTIONS: &[NativeFunction] = &[NativeFunction {
+    name: "format",
+    arity: 1,
+    max_arity: None,
+    function: format,
+}];
+
+/// The largest width or precision a placeholder may ask for.
+const MAX_WIDTH: usize = 10_000;
+
+/// How a placeholder lays out its value: `{:[[fill]align][0][width][.precision][type]}`.
+#[derive(Debug, Default)]
+struct Spec {
+    fill: Option<char>,
+    align: Option<char>,
+    zero: bool,
+    width: usize,
+    precision: Option<usize>,
+    kind: Option<char>,
+}
+
+/// Replaces each placeholder in the template with an argument. `{}` takes the next
+/// argument, `{1}` a numbered one and `{name}` a key of the map passed as the last argument;
+/// `{{` and `}}` stand for braces.
+fn format(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let template = expect_string("format", &arguments[0], token)?;
+    let arguments = &arguments[1..];
+
+    let error = |message: String| InterpreterError {
+        token: Some(token.clone()),
+        message,
+        kind: ErrorKind::Runtime,
+    };
+
+    let mut result = String::new();
+    let mut next = 0;
+    let mut characters = template.chars().peekable();
+
+    while let Some(character) = characters.next() {
+        match character {
+            '{' if characters.next_if_eq(&'{').is_some() => result.push('{'),
+            '}' if characters.next_if_eq(&'}').is_some() => result.push('}'),
+            '}' => return Err(error("Unmatched '}' in format string.".into())),
+            '{' => {
+                let mut placeholder = String::new();
+                loop {
+                    match characters.next() {
+                        Some('}') => break,
+                        Some(character) => placeholder.push(character),
+                        None => return Err(error("Unmatched '{' in format string.".into())),
+                    }
+                }
+
+                let (name, spec) = placeholder
+                    .split_once(':')
+                    .unwrap_or((placeholder.as_str(), ""));
+
+                let value = if name.is_empty() {
+                    next += 1;
+                    arguments.get(next - 1).cloned()
+                } else if let Ok(index) = name.parse::<usize>() {
+                    arguments.get(index).cloned()
+                } else {
+                    match arguments.last() {
+                        Some(Value::Map(map)) => map.borrow().get(name).cloned(),
+                        _ => None,
+                    }
+                };
+
+                let Some(value) = value else {
+                    return Err(error(format!(
+                        "Missing argument for placeholder '{{{name}}}'."
+                    )));
+                };
+
+                let spec = parse_spec(&mut spec.chars().peekable())
+                    .ok_or_else(|| error(format!("Invalid format spec '{spec}'.")))?;
+
+                // Numbers keep their value for the numeric specs; anything else is shown as
+                // `print` would, calling `__str__` on instances however deeply nested.
+                let value = match value {
+                    Value::Number(_) => value,
+                    value => string(interpreter.stringify(&value)?),
+                };
+
+                result.push_str(&apply(&spec, &value).map_err(error)?);
+            }
+            character => result.push(character),
+        }
+    }
+
+    Ok(string(result))
+}
+
+fn parse_spec(characters: &mut Peekable<Chars>) -> Option<Spec> {
+    let mut spec = Spec::default();
+    let is_align = |character: char| matches!(character, '<' | '>' | '^');
+
+    let rest: Vec<char> = characters.clone().collect();
+    match rest.as_slice() {
+        [fill, align, ..] if is_align(*align) => {
+            spec.fill = Some(*fill);
+            spec.align = Some(*align);
+            characters.nth(1);
+        }
+        [align, ..] if is_align(*align) => {
+            spec.align = Some(*align);
+            characters.next();
+        }
+        _ => {}
+    }
+
+    spec.zero = characters.next_if_eq(&'0').is_some();
+    spec.width = match digits(characters).as_str() {
+        "" => 0,
+        width => limited(width)?,
+    };
+
+    if characters.next_if_eq(&'.').is_some() {
+        spec.precision = Some(limited(&digits(characters))?);
+    }
+
+    spec.kind = characters.next_if(|character| "xXbo".contains(*character));
+
+    match characters.next() {
+        None => Some(spec),
+        Some(_) => None,
+    }
+}
+
+fn digits(characters: &mut Peekable<Chars>) -> String {
+    let mut digits = String::new();
+    while let Some(digit) = characters.next_if(char::is_ascii_digit) {
+        digits.push(digit);
+    }
+
+    digits
+}
+
+/// Parses a width or precision, which can't be larger than `MAX_WIDTH`.
+fn limited(digits: &str) -> Option<usize> {
+    digits.parse().ok().filter(|value| *value <= MAX_WIDTH)
+}
+
+fn apply(spec: &Spec, value: &Value) -> Result<String, String> {
+    let text = match (value, spec.kind) {
+        (Value::Number(number), Some(kind)) => {
+            if number.fract() != 0.0 || number.abs() >= 2f64.powi(63) {
+                return Err(format!("Format type '{kind}' requires an integer."));
+            }
+
+            let integer = *number as i64;
+            let digits = match kind {
+                'x' => format!("{:x}", integer.unsigned_abs()),
+                'X' => format!("{:X}", integer.unsigned_abs()),
+                'b' => format!("{:b}", integer.unsigned_abs()),
+                _ => format!("{:o}", integer.unsigned_abs()),
+            };
+
+            if integer < 0 {
+                format!("-{digits}")
+            } else {
+                digits
+            }
+        }
+        (_, Some(kind)) => return Err(format!("Format type '{kind}' requires an integer.")),
+        (Value::Number(number), None) => match spec.precision {
+            Some(precision) => format!("{number:.precision$}"),
+            None => number.to_string(),
+        },
+        (value, None) => match spec.precision {
+            Some(precision) => value.to_string().chars().take(precision).collect(),
+            None => value.to_string(),
+        },
+    };
+
+    let length = text.chars().count();
+    if length >= spec.width {
+        return Ok(text);
+    }
+    let padding = spec.width - length;
+
+    // Zero padding goes between the sign and the digits.
+    if spec.zero && spec.align.is_none() && matches!(value, Value::Number(_)) {
+        let (sign, digits) = match text.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", text.as_str()),
+        };
+
+        return Ok(format!("{sign}{}{digits}", "0".repeat(padding)));
+    }
+
+    let fill = spec.fill.unwrap_or(' ').to_string();
+    let align = spec.align.unwrap_or(match value {
+        Value::Number(_) => '>',
+        _ => '<',
+    });
+
+    Ok(match align {
+        '<' => format!("{text}{}", fill.repeat(padding)),
+        '>' => format!("{}{text}", fill.repeat(padding)),
+        _ => format!(
+            "{}{text}{}",
+            fill.repeat(padding / 2),
+            fill.repeat(padding - padding / 2)
+        ),
+    })
+}
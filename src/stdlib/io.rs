@@ -0,0 +1,230 @@
+use std::{
+    cell::RefCell,
+    fs::{self, OpenOptions},
+    io::{self, Read, Write},
+    path::PathBuf,
+    rc::Rc,
+};
+
+use crate::{
+    native::NativeFunction, ErrorKind, EvaluateInterpreterResult, Interpreter, InterpreterError,
+    Token, Value,
+};
+
+use super::{denied, expect_string, string};
+
+pub const FUNCTIONS: &[NativeFunction] = &[
+    NativeFunction {
+        name: "read_line",
+        arity: 0,
+        max_arity: Some(0),
+        function: read_line,
+    },
+    NativeFunction {
+        name: "read_all_stdin",
+        arity: 0,
+        max_arity: Some(0),
+        function: read_all_stdin,
+    },
+    NativeFunction {
+        name: "read_file",
+        arity: 1,
+        max_arity: Some(1),
+        function: read_file,
+    },
+    NativeFunction {
+        name: "write_file",
+        arity: 2,
+        max_arity: Some(2),
+        function: write_file,
+    },
+    NativeFunction {
+        name: "append_file",
+        arity: 2,
+        max_arity: Some(2),
+        function: append_file,
+    },
+    NativeFunction {
+        name: "exists",
+        arity: 1,
+        max_arity: Some(1),
+        function: exists,
+    },
+    NativeFunction {
+        name: "list_dir",
+        arity: 1,
+        max_arity: Some(1),
+        function: list_dir,
+    },
+    NativeFunction {
+        name: "remove_file",
+        arity: 1,
+        max_arity: Some(1),
+        function: remove_file,
+    },
+];
+
+fn io_error(action: &str, subject: &str, error: io::Error, token: &Token) -> InterpreterError {
+    InterpreterError {
+        token: Some(token.clone()),
+        message: format!("Could not {action} '{subject}': {error}."),
+        kind: ErrorKind::Runtime,
+    }
+}
+
+/// The path named by the argument of the native `name`, if the capabilities allow it.
+fn allowed_path(
+    interpreter: &Interpreter,
+    name: &str,
+    value: &Value,
+    token: &Token,
+) -> Result<PathBuf, InterpreterError> {
+    let path = expect_string(name, value, token)?;
+
+    match interpreter.capabilities.check_path(&path) {
+        Ok(Some(resolved)) => Ok(resolved),
+        Ok(None) => Err(denied(&path, token)),
+        Err(error) => Err(io_error("resolve", &path, error, token)),
+    }
+}
+
+fn check_stdin(interpreter: &Interpreter, token: &Token) -> Result<(), InterpreterError> {
+    match interpreter.capabilities.stdin {
+        true => Ok(()),
+        false => Err(denied("standard input", token)),
+    }
+}
+
+/// The next line of standard input without its line ending, or nil at the end of input.
+fn read_line(
+    interpreter: &mut Interpreter,
+    _: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    check_stdin(interpreter, token)?;
+
+    let mut line = String::new();
+    let read = io::stdin()
+        .read_line(&mut line)
+        .map_err(|error| io_error("read", "standard input", error, token))?;
+
+    if read == 0 {
+        return Ok(Value::Nil);
+    }
+
+    let length = line.trim_end_matches(['\n', '\r']).len();
+    line.truncate(length);
+
+    Ok(string(line))
+}
+
+fn read_all_stdin(
+    interpreter: &mut Interpreter,
+    _: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    check_stdin(interpreter, token)?;
+
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|error| io_error("read", "standard input", error, token))?;
+
+    Ok(string(input))
+}
+
+fn read_file(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let path = allowed_path(interpreter, "read_file", &arguments[0], token)?;
+
+    let contents = fs::read_to_string(&path)
+        .map_err(|error| io_error("read file", &path.display().to_string(), error, token))?;
+
+    Ok(string(contents))
+}
+
+/// Replaces the contents of a file, creating it if necessary.
+fn write_file(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let path = allowed_path(interpreter, "write_file", &arguments[0], token)?;
+    let contents = expect_string("write_file", &arguments[1], token)?;
+
+    fs::write(&path, contents.as_bytes())
+        .map_err(|error| io_error("write file", &path.display().to_string(), error, token))?;
+
+    Ok(Value::Nil)
+}
+
+/// Adds to the end of a file, creating it if necessary.
+fn append_file(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let path = allowed_path(interpreter, "append_file", &arguments[0], token)?;
+    let contents = expect_string("append_file", &arguments[1], token)?;
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .map_err(|error| io_error("append to file", &path.display().to_string(), error, token))?;
+
+    Ok(Value::Nil)
+}
+
+fn exists(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let path = expect_string("exists", &arguments[0], token)?;
+
+    // A path whose directory doesn't exist can't be resolved, and doesn't exist either.
+    match interpreter.capabilities.check_path(&path) {
+        Ok(Some(resolved)) => Ok(Value::Boolean(resolved.exists())),
+        Ok(None) => Err(denied(&path, token)),
+        Err(_) => Ok(Value::Boolean(false)),
+    }
+}
+
+/// The names of the entries in a directory, sorted.
+fn list_dir(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let path = allowed_path(interpreter, "list_dir", &arguments[0], token)?;
+    let describe = |error| io_error("list directory", &path.display().to_string(), error, token);
+
+    let mut names = fs::read_dir(&path)
+        .map_err(describe)?
+        .map(|entry| entry.map(|entry| entry.file_name().to_string_lossy().into_owned()))
+        .collect::<io::Result<Vec<String>>>()
+        .map_err(describe)?;
+    names.sort();
+
+    let names = names.into_iter().map(string).collect();
+
+    Ok(Value::List(Rc::new(RefCell::new(names))))
+}
+
+fn remove_file(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let path = allowed_path(interpreter, "remove_file", &arguments[0], token)?;
+
+    fs::remove_file(&path)
+        .map_err(|error| io_error("remove file", &path.display().to_string(), error, token))?;
+
+    Ok(Value::Nil)
+}
@@ -0,0 +1,465 @@
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+
+use crate::{
+    native::NativeFunction, ErrorKind, EvaluateInterpreterResult, Interpreter, InterpreterError,
+    Token, Value,
+};
+
+use super::{argument_error, expect_integer, expect_string, string};
+
+pub const FUNCTIONS: &[NativeFunction] = &[
+    NativeFunction {
+        name: "json_parse",
+        arity: 1,
+        max_arity: Some(1),
+        function: json_parse,
+    },
+    NativeFunction {
+        name: "json_stringify",
+        arity: 1,
+        max_arity: Some(2),
+        function: json_stringify,
+    },
+];
+
+fn json_parse(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let text = expect_string("json_parse", &arguments[0], token)?;
+
+    let mut parser = JsonParser::new(&text);
+    parser.document().map_err(|message| InterpreterError {
+        token: Some(token.clone()),
+        message: format!(
+            "Invalid JSON at line {}, column {}: {message}",
+            parser.line, parser.column
+        ),
+        kind: ErrorKind::Runtime,
+    })
+}
+
+/// Serializes a value, on one line or with `indent` spaces per level of nesting.
+fn json_stringify(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let indent = match arguments.get(1) {
+        Some(indent) => match expect_integer("json_stringify", indent, token)? {
+            indent @ 0..=10 => Some(indent as usize),
+            _ => {
+                return Err(argument_error(
+                    "json_stringify",
+                    "an indent from 0 to 10",
+                    token,
+                ))
+            }
+        },
+        None => None,
+    };
+
+    let mut writer = JsonWriter {
+        output: String::new(),
+        indent,
+        visiting: Vec::new(),
+    };
+
+    writer
+        .value(&arguments[0], 0)
+        .map_err(|message| InterpreterError {
+            token: Some(token.clone()),
+            message,
+            kind: ErrorKind::Runtime,
+        })?;
+
+    Ok(string(writer.output))
+}
+
+/// How deeply arrays and objects may nest, which keeps the parser's recursion well within
+/// the stack.
+const MAX_DEPTH: usize = 512;
+
+/// A recursive descent parser over the characters of a JSON document, tracking the
+/// position for error messages.
+struct JsonParser {
+    characters: Vec<char>,
+    current: usize,
+    line: usize,
+    column: usize,
+    /// The number of arrays and objects the parser is inside.
+    depth: usize,
+}
+
+type JsonResult<T> = Result<T, String>;
+
+impl JsonParser {
+    fn new(text: &str) -> Self {
+        JsonParser {
+            characters: text.chars().collect(),
+            current: 0,
+            line: 1,
+            column: 1,
+            depth: 0,
+        }
+    }
+
+    fn document(&mut self) -> JsonResult<Value> {
+        let value = self.value()?;
+        self.skip_whitespace();
+
+        match self.peek() {
+            None => Ok(value),
+            Some(_) => Err("Expect end of input after value.".into()),
+        }
+    }
+
+    fn value(&mut self) -> JsonResult<Value> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some('{') => self.nested(Self::object),
+            Some('[') => self.nested(Self::array),
+            Some('"') => Ok(string(self.string()?)),
+            Some('-' | '0'..='9') => self.number(),
+            Some('t') => self.keyword("true", Value::Boolean(true)),
+            Some('f') => self.keyword("false", Value::Boolean(false)),
+            Some('n') => self.keyword("null", Value::Nil),
+            Some(_) => Err("Expect value.".into()),
+            None => Err("Unexpected end of input.".into()),
+        }
+    }
+
+    fn nested(&mut self, parse: fn(&mut Self) -> JsonResult<Value>) -> JsonResult<Value> {
+        if self.depth == MAX_DEPTH {
+            return Err(format!("Nesting deeper than {MAX_DEPTH} levels."));
+        }
+
+        self.depth += 1;
+        let value = parse(self)?;
+        self.depth -= 1;
+
+        Ok(value)
+    }
+
+    fn object(&mut self) -> JsonResult<Value> {
+        self.advance();
+        let mut map: BTreeMap<String, Value> = BTreeMap::new();
+
+        self.skip_whitespace();
+        if self.match_('}') {
+            return Ok(Value::Map(Rc::new(RefCell::new(map))));
+        }
+
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return Err("Expect string key.".into());
+            }
+
+            let key = self.string()?;
+
+            self.skip_whitespace();
+            if !self.match_(':') {
+                return Err("Expect ':' after key.".into());
+            }
+
+            let value = self.value()?;
+            map.insert(key, value);
+
+            self.skip_whitespace();
+            if self.match_('}') {
+                return Ok(Value::Map(Rc::new(RefCell::new(map))));
+            }
+
+            if !self.match_(',') {
+                return Err("Expect ',' or '}' after object member.".into());
+            }
+        }
+    }
+
+    fn array(&mut self) -> JsonResult<Value> {
+        self.advance();
+        let mut list: Vec<Value> = Vec::new();
+
+        self.skip_whitespace();
+        if self.match_(']') {
+            return Ok(Value::List(Rc::new(RefCell::new(list))));
+        }
+
+        loop {
+            list.push(self.value()?);
+
+            self.skip_whitespace();
+            if self.match_(']') {
+                return Ok(Value::List(Rc::new(RefCell::new(list))));
+            }
+
+            if !self.match_(',') {
+                return Err("Expect ',' or ']' after array element.".into());
+            }
+        }
+    }
+
+    fn string(&mut self) -> JsonResult<String> {
+        self.advance();
+        let mut result = String::new();
+
+        loop {
+            match self.advance() {
+                Some('"') => return Ok(result),
+                Some('\\') => result.push(self.escape()?),
+                Some(character) if (character as u32) < 0x20 => {
+                    return Err("Unescaped control character in string.".into());
+                }
+                Some(character) => result.push(character),
+                None => return Err("Unterminated string.".into()),
+            }
+        }
+    }
+
+    fn escape(&mut self) -> JsonResult<char> {
+        match self.advance() {
+            Some('"') => Ok('"'),
+            Some('\\') => Ok('\\'),
+            Some('/') => Ok('/'),
+            Some('b') => Ok('\u{8}'),
+            Some('f') => Ok('\u{c}'),
+            Some('n') => Ok('\n'),
+            Some('r') => Ok('\r'),
+            Some('t') => Ok('\t'),
+            Some('u') => {
+                let high = self.hex_escape()?;
+
+                if !(0xD800..0xDC00).contains(&high) {
+                    return char::from_u32(high).ok_or_else(|| "Invalid unicode escape.".into());
+                }
+
+                // A character outside the basic plane is written as a surrogate pair.
+                if !(self.match_('\\') && self.match_('u')) {
+                    return Err("Expect low surrogate after high surrogate.".into());
+                }
+
+                let low = self.hex_escape()?;
+                if !(0xDC00..0xE000).contains(&low) {
+                    return Err("Invalid low surrogate.".into());
+                }
+
+                let code = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+                char::from_u32(code).ok_or_else(|| "Invalid unicode escape.".into())
+            }
+            Some(_) => Err("Invalid escape sequence.".into()),
+            None => Err("Unterminated string.".into()),
+        }
+    }
+
+    fn hex_escape(&mut self) -> JsonResult<u32> {
+        let mut code = 0;
+
+        for _ in 0..4 {
+            let digit = self
+                .advance()
+                .and_then(|character| character.to_digit(16))
+                .ok_or("Expect four hex digits in unicode escape.")?;
+            code = code * 16 + digit;
+        }
+
+        Ok(code)
+    }
+
+    fn number(&mut self) -> JsonResult<Value> {
+        let start = self.current;
+
+        self.match_('-');
+
+        // A leading zero can't be followed by more digits.
+        if !self.match_('0') && !self.digits() {
+            return Err("Expect digit.".into());
+        }
+
+        if self.match_('.') && !self.digits() {
+            return Err("Expect digit after decimal point.".into());
+        }
+
+        if self.match_('e') || self.match_('E') {
+            if !self.match_('+') {
+                self.match_('-');
+            }
+
+            if !self.digits() {
+                return Err("Expect digit in exponent.".into());
+            }
+        }
+
+        let text: String = self.characters[start..self.current].iter().collect();
+        text.parse()
+            .map(Value::Number)
+            .map_err(|_| "Invalid number.".into())
+    }
+
+    /// Consumes a run of digits, returning whether there was at least one.
+    fn digits(&mut self) -> bool {
+        let start = self.current;
+
+        while self
+            .peek()
+            .is_some_and(|character| character.is_ascii_digit())
+        {
+            self.advance();
+        }
+
+        self.current > start
+    }
+
+    fn keyword(&mut self, keyword: &str, value: Value) -> JsonResult<Value> {
+        for expected in keyword.chars() {
+            if !self.match_(expected) {
+                return Err("Expect value.".into());
+            }
+        }
+
+        Ok(value)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.advance();
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.characters.get(self.current).copied()
+    }
+
+    fn match_(&mut self, expected: char) -> bool {
+        if self.peek() != Some(expected) {
+            return false;
+        }
+
+        self.advance();
+        true
+    }
+
+    fn advance(&mut self) -> Option<char> {
+        let character = self.peek()?;
+        self.current += 1;
+
+        if character == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+
+        Some(character)
+    }
+}
+
+struct JsonWriter {
+    output: String,
+    indent: Option<usize>,
+    /// The lists and maps being written, to detect a value that contains itself.
+    visiting: Vec<*const ()>,
+}
+
+impl JsonWriter {
+    fn value(&mut self, value: &Value, depth: usize) -> Result<(), String> {
+        match value {
+            Value::Nil => self.output.push_str("null"),
+            Value::Boolean(boolean) => self.output.push_str(&boolean.to_string()),
+            Value::Number(number) if number.is_finite() => {
+                self.output.push_str(&number.to_string())
+            }
+            Value::Number(number) => return Err(format!("Cannot serialize {number} to JSON.")),
+            Value::String(string) => self.string(string),
+            Value::List(list) => {
+                self.enter(Rc::as_ptr(list) as *const ())?;
+
+                let list = list.borrow();
+                self.output.push('[');
+                for (index, element) in list.iter().enumerate() {
+                    self.separator(index, depth + 1);
+                    self.value(element, depth + 1)?;
+                }
+                self.close(list.is_empty(), depth, ']');
+
+                self.visiting.pop();
+            }
+            Value::Map(map) => {
+                self.enter(Rc::as_ptr(map) as *const ())?;
+
+                let map = map.borrow();
+                self.output.push('{');
+                for (index, (key, value)) in map.iter().enumerate() {
+                    self.separator(index, depth + 1);
+                    self.string(key);
+                    self.output
+                        .push_str(if self.indent.is_some() { ": " } else { ":" });
+                    self.value(value, depth + 1)?;
+                }
+                self.close(map.is_empty(), depth, '}');
+
+                self.visiting.pop();
+            }
+            value => return Err(format!("Cannot serialize {} to JSON.", value.type_name())),
+        }
+
+        Ok(())
+    }
+
+    fn enter(&mut self, pointer: *const ()) -> Result<(), String> {
+        if self.visiting.contains(&pointer) {
+            return Err("Cannot serialize a value that contains itself to JSON.".into());
+        }
+
+        self.visiting.push(pointer);
+        Ok(())
+    }
+
+    /// Writes what precedes the element at `index` of a list or map.
+    fn separator(&mut self, index: usize, depth: usize) {
+        if index > 0 {
+            self.output.push(',');
+        }
+
+        self.newline(depth);
+    }
+
+    fn close(&mut self, empty: bool, depth: usize, delimiter: char) {
+        if !empty {
+            self.newline(depth);
+        }
+
+        self.output.push(delimiter);
+    }
+
+    fn newline(&mut self, depth: usize) {
+        if let Some(indent) = self.indent {
+            self.output.push('\n');
+            self.output.push_str(&" ".repeat(indent * depth));
+        }
+    }
+
+    fn string(&mut self, string: &str) {
+        self.output.push('"');
+
+        for character in string.chars() {
+            match character {
+                '"' => self.output.push_str("\\\""),
+                '\\' => self.output.push_str("\\\\"),
+                '\n' => self.output.push_str("\\n"),
+                '\r' => self.output.push_str("\\r"),
+                '\t' => self.output.push_str("\\t"),
+                '\u{8}' => self.output.push_str("\\b"),
+                '\u{c}' => self.output.push_str("\\f"),
+                character if (character as u32) < 0x20 => self
+                    .output
+                    .push_str(&format!("\\u{:04x}", character as u32)),
+                character => self.output.push(character),
+            }
+        }
+
+        self.output.push('"');
+    }
+}
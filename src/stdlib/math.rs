@@ -0,0 +1,275 @@
+use crate::{
+    native::NativeFunction, Environment, EvaluateInterpreterResult, Interpreter, Token, Value,
+};
+
+use super::expect_number;
+
+pub const FUNCTIONS: &[NativeFunction] = &[
+    NativeFunction {
+        name: "sqrt",
+        arity: 1,
+        max_arity: Some(1),
+        function: sqrt,
+    },
+    NativeFunction {
+        name: "abs",
+        arity: 1,
+        max_arity: Some(1),
+        function: abs,
+    },
+    NativeFunction {
+        name: "floor",
+        arity: 1,
+        max_arity: Some(1),
+        function: floor,
+    },
+    NativeFunction {
+        name: "ceil",
+        arity: 1,
+        max_arity: Some(1),
+        function: ceil,
+    },
+    NativeFunction {
+        name: "round",
+        arity: 1,
+        max_arity: Some(1),
+        function: round,
+    },
+    NativeFunction {
+        name: "sin",
+        arity: 1,
+        max_arity: Some(1),
+        function: sin,
+    },
+    NativeFunction {
+        name: "cos",
+        arity: 1,
+        max_arity: Some(1),
+        function: cos,
+    },
+    NativeFunction {
+        name: "tan",
+        arity: 1,
+        max_arity: Some(1),
+        function: tan,
+    },
+    NativeFunction {
+        name: "asin",
+        arity: 1,
+        max_arity: Some(1),
+        function: asin,
+    },
+    NativeFunction {
+        name: "acos",
+        arity: 1,
+        max_arity: Some(1),
+        function: acos,
+    },
+    NativeFunction {
+        name: "atan",
+        arity: 1,
+        max_arity: Some(1),
+        function: atan,
+    },
+    NativeFunction {
+        name: "exp",
+        arity: 1,
+        max_arity: Some(1),
+        function: exp,
+    },
+    NativeFunction {
+        name: "log2",
+        arity: 1,
+        max_arity: Some(1),
+        function: log2,
+    },
+    NativeFunction {
+        name: "log10",
+        arity: 1,
+        max_arity: Some(1),
+        function: log10,
+    },
+    NativeFunction {
+        name: "pow",
+        arity: 2,
+        max_arity: Some(2),
+        function: pow,
+    },
+    NativeFunction {
+        name: "atan2",
+        arity: 2,
+        max_arity: Some(2),
+        function: atan2,
+    },
+    NativeFunction {
+        name: "log",
+        arity: 1,
+        max_arity: Some(2),
+        function: log,
+    },
+    NativeFunction {
+        name: "min",
+        arity: 1,
+        max_arity: None,
+        function: min,
+    },
+    NativeFunction {
+        name: "max",
+        arity: 1,
+        max_arity: None,
+        function: max,
+    },
+    NativeFunction {
+        name: "is_nan",
+        arity: 1,
+        max_arity: Some(1),
+        function: is_nan,
+    },
+    NativeFunction {
+        name: "is_finite",
+        arity: 1,
+        max_arity: Some(1),
+        function: is_finite,
+    },
+];
+
+pub fn define_constants(builtins: &mut Environment) {
+    builtins.define_constant("PI".into(), Value::Number(std::f64::consts::PI));
+    builtins.define_constant("E".into(), Value::Number(std::f64::consts::E));
+    builtins.define_constant("INFINITY".into(), Value::Number(f64::INFINITY));
+    builtins.define_constant("NAN".into(), Value::Number(f64::NAN));
+}
+
+/// Applies `function` to the single number argument of the native `name`.
+fn unary(
+    name: &str,
+    function: fn(f64) -> f64,
+    arguments: &[Value],
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let number = expect_number(name, &arguments[0], token)?;
+
+    Ok(Value::Number(function(number)))
+}
+
+fn sqrt(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    unary("sqrt", f64::sqrt, &arguments, token)
+}
+
+fn abs(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    unary("abs", f64::abs, &arguments, token)
+}
+
+fn floor(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    unary("floor", f64::floor, &arguments, token)
+}
+
+fn ceil(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    unary("ceil", f64::ceil, &arguments, token)
+}
+
+fn round(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    unary("round", f64::round, &arguments, token)
+}
+
+fn sin(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    unary("sin", f64::sin, &arguments, token)
+}
+
+fn cos(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    unary("cos", f64::cos, &arguments, token)
+}
+
+fn tan(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    unary("tan", f64::tan, &arguments, token)
+}
+
+fn asin(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    unary("asin", f64::asin, &arguments, token)
+}
+
+fn acos(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    unary("acos", f64::acos, &arguments, token)
+}
+
+fn atan(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    unary("atan", f64::atan, &arguments, token)
+}
+
+fn exp(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    unary("exp", f64::exp, &arguments, token)
+}
+
+fn log2(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    unary("log2", f64::log2, &arguments, token)
+}
+
+fn log10(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    unary("log10", f64::log10, &arguments, token)
+}
+
+fn pow(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    let base = expect_number("pow", &arguments[0], token)?;
+    let exponent = expect_number("pow", &arguments[1], token)?;
+
+    Ok(Value::Number(base.powf(exponent)))
+}
+
+fn atan2(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    let y = expect_number("atan2", &arguments[0], token)?;
+    let x = expect_number("atan2", &arguments[1], token)?;
+
+    Ok(Value::Number(y.atan2(x)))
+}
+
+/// The natural logarithm, or the logarithm in the given base.
+fn log(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    let number = expect_number("log", &arguments[0], token)?;
+
+    match arguments.get(1) {
+        Some(base) => Ok(Value::Number(
+            number.log(expect_number("log", base, token)?),
+        )),
+        None => Ok(Value::Number(number.ln())),
+    }
+}
+
+fn min(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    fold("min", f64::min, &arguments, token)
+}
+
+fn max(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    fold("max", f64::max, &arguments, token)
+}
+
+/// Combines the number arguments of the native `name` from left to right.
+fn fold(
+    name: &str,
+    function: fn(f64, f64) -> f64,
+    arguments: &[Value],
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let mut result = expect_number(name, &arguments[0], token)?;
+
+    for argument in &arguments[1..] {
+        result = function(result, expect_number(name, argument, token)?);
+    }
+
+    Ok(Value::Number(result))
+}
+
+fn is_nan(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    let number = expect_number("is_nan", &arguments[0], token)?;
+
+    Ok(Value::Boolean(number.is_nan()))
+}
+
+fn is_finite(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let number = expect_number("is_finite", &arguments[0], token)?;
+
+    Ok(Value::Boolean(number.is_finite()))
+}
@@ -0,0 +1,100 @@
+//! The native functions every script and module can see, grouped by topic.
+
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    native::NativeFunction, Callable, Environment, ErrorKind, InterpreterError, Token, Value,
+};
+
+mod collections;
+mod format;
+mod io;
+mod json;
+mod math;
+mod random;
+mod regex;
+mod strings;
+mod system;
+mod time;
+mod types;
+
+/// Defines every native function in `builtins`.
+pub fn define(builtins: &mut Environment) {
+    define_functions(builtins, collections::FUNCTIONS);
+    define_functions(builtins, format::FUNCTIONS);
+    define_functions(builtins, io::FUNCTIONS);
+    define_functions(builtins, json::FUNCTIONS);
+    define_functions(builtins, math::FUNCTIONS);
+    define_functions(builtins, random::FUNCTIONS);
+    define_functions(builtins, regex::FUNCTIONS);
+    define_functions(builtins, strings::FUNCTIONS);
+    define_functions(builtins, system::FUNCTIONS);
+    define_functions(builtins, time::FUNCTIONS);
+    define_functions(builtins, types::FUNCTIONS);
+
+    math::define_constants(builtins);
+}
+
+fn define_functions(builtins: &mut Environment, functions: &[NativeFunction]) {
+    for function in functions.iter().cloned() {
+        builtins.define(
+            function.name.into(),
+            Value::Function(Rc::new(RefCell::new(function))),
+        );
+    }
+}
+
+/// Reports an argument of the wrong type, e.g. "Argument to 'arity' must be a function."
+fn argument_error(name: &str, expected: &str, token: &Token) -> InterpreterError {
+    InterpreterError {
+        token: Some(token.clone()),
+        message: format!("Argument to '{name}' must be {expected}."),
+        kind: ErrorKind::Runtime,
+    }
+}
+
+/// Reports an access the interpreter's capabilities don't allow.
+fn denied(subject: &str, token: &Token) -> InterpreterError {
+    InterpreterError {
+        token: Some(token.clone()),
+        message: format!("Access to '{subject}' is not allowed."),
+        kind: ErrorKind::Runtime,
+    }
+}
+
+fn expect_function(
+    name: &str,
+    value: &Value,
+    token: &Token,
+) -> Result<Rc<RefCell<dyn Callable>>, InterpreterError> {
+    match value {
+        Value::Function(callable) => Ok(callable.clone()),
+        Value::Class(class) => Ok(Rc::new(RefCell::new(class.clone()))),
+        _ => Err(argument_error(name, "a function", token)),
+    }
+}
+
+fn expect_number(name: &str, value: &Value, token: &Token) -> Result<f64, InterpreterError> {
+    match value {
+        Value::Number(number) => Ok(*number),
+        _ => Err(argument_error(name, "a number", token)),
+    }
+}
+
+fn expect_string(name: &str, value: &Value, token: &Token) -> Result<Rc<String>, InterpreterError> {
+    match value {
+        Value::String(string) => Ok(string.clone()),
+        _ => Err(argument_error(name, "a string", token)),
+    }
+}
+
+fn expect_integer(name: &str, value: &Value, token: &Token) -> Result<i64, InterpreterError> {
+    match value {
+        Value::Number(number) if number.fract() == 0.0 => Ok(*number as i64),
+        _ => Err(argument_error(name, "an integer", token)),
+    }
+}
+
+fn string(value: impl Into<String>) -> Value {
+    Value::String(Rc::new(value.into()))
+}
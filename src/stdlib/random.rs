@@ -0,0 +1,121 @@
+use crate::{
+    native::NativeFunction, ErrorKind, EvaluateInterpreterResult, Interpreter, InterpreterError,
+    Random, Token, Value,
+};
+
+use super::{argument_error, expect_integer};
+
+pub const FUNCTIONS: &[NativeFunction] = &[
+    NativeFunction {
+        name: "random",
+        arity: 0,
+        max_arity: Some(0),
+        function: random,
+    },
+    NativeFunction {
+        name: "random_int",
+        arity: 2,
+        max_arity: Some(2),
+        function: random_int,
+    },
+    NativeFunction {
+        name: "shuffle",
+        arity: 1,
+        max_arity: Some(1),
+        function: shuffle,
+    },
+    NativeFunction {
+        name: "choice",
+        arity: 1,
+        max_arity: Some(1),
+        function: choice,
+    },
+    NativeFunction {
+        name: "seed",
+        arity: 1,
+        max_arity: Some(1),
+        function: seed,
+    },
+];
+
+/// A number in `[0, 1)`.
+fn random(interpreter: &mut Interpreter, _: Vec<Value>, _: &Token) -> EvaluateInterpreterResult {
+    Ok(Value::Number(interpreter.random.next_f64()))
+}
+
+/// An integer from `low` to `high`, both included.
+fn random_int(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let low = expect_integer("random_int", &arguments[0], token)?;
+    let high = expect_integer("random_int", &arguments[1], token)?;
+
+    if low > high {
+        return Err(InterpreterError {
+            token: Some(token.clone()),
+            message: format!("Empty range from {low} to {high}."),
+            kind: ErrorKind::Runtime,
+        });
+    }
+
+    let span = high.abs_diff(low).wrapping_add(1);
+    let offset = match span {
+        // The range covers every 64-bit integer.
+        0 => interpreter.random.next_u64(),
+        span => interpreter.random.below(span),
+    };
+
+    Ok(Value::Number(low.wrapping_add_unsigned(offset) as f64))
+}
+
+/// Shuffles a list in place.
+fn shuffle(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let Value::List(list) = &arguments[0] else {
+        return Err(argument_error("shuffle", "a list", token));
+    };
+
+    let mut list = list.borrow_mut();
+    for index in (1..list.len()).rev() {
+        let other = interpreter.random.below(index as u64 + 1) as usize;
+        list.swap(index, other);
+    }
+
+    Ok(Value::Nil)
+}
+
+fn choice(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let Value::List(list) = &arguments[0] else {
+        return Err(argument_error("choice", "a list", token));
+    };
+
+    let list = list.borrow();
+    if list.is_empty() {
+        return Err(argument_error("choice", "a non-empty list", token));
+    }
+
+    let index = interpreter.random.below(list.len() as u64) as usize;
+
+    Ok(list[index].clone())
+}
+
+/// Restarts the generator, so that the same seed produces the same numbers.
+fn seed(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let seed = expect_integer("seed", &arguments[0], token)?;
+    interpreter.random = Random::new(seed as u64);
+
+    Ok(Value::Nil)
+}
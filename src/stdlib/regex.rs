@@ -0,0 +1,283 @@
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+
+use crate::{
+    native::NativeFunction, Captures, ErrorKind, EvaluateInterpreterResult, Interpreter,
+    InterpreterError, Regex, Token, TooComplex, Value,
+};
+
+use super::{expect_string, string};
+
+pub const FUNCTIONS: &[NativeFunction] = &[
+    NativeFunction {
+        name: "regex_match",
+        arity: 2,
+        max_arity: Some(2),
+        function: regex_match,
+    },
+    NativeFunction {
+        name: "regex_find_all",
+        arity: 2,
+        max_arity: Some(2),
+        function: regex_find_all,
+    },
+    NativeFunction {
+        name: "regex_replace",
+        arity: 3,
+        max_arity: Some(3),
+        function: regex_replace,
+    },
+    NativeFunction {
+        name: "regex_split",
+        arity: 2,
+        max_arity: Some(2),
+        function: regex_split,
+    },
+];
+
+/// How many compiled patterns the interpreter keeps before starting afresh.
+const CACHE_SIZE: usize = 256;
+
+/// The compiled form of the pattern argument of the native `name`, from the interpreter's
+/// cache if it was used before.
+fn compile(
+    interpreter: &mut Interpreter,
+    name: &str,
+    value: &Value,
+    token: &Token,
+) -> Result<Rc<Regex>, InterpreterError> {
+    let pattern = expect_string(name, value, token)?;
+
+    if let Some(regex) = interpreter.regexes.get(pattern.as_str()) {
+        return Ok(regex.clone());
+    }
+
+    let regex = Rc::new(Regex::new(&pattern).map_err(|error| InterpreterError {
+        token: Some(token.clone()),
+        message: format!("Invalid regular expression: {error}."),
+        kind: ErrorKind::Runtime,
+    })?);
+
+    if interpreter.regexes.len() >= CACHE_SIZE {
+        interpreter.regexes.clear();
+    }
+    interpreter
+        .regexes
+        .insert(pattern.to_string(), regex.clone());
+
+    Ok(regex)
+}
+
+/// Reports a search that ran out of steps, such as `(a*)*b` against a long run of `a`s.
+fn too_complex(token: &Token, error: TooComplex) -> InterpreterError {
+    InterpreterError {
+        token: Some(token.clone()),
+        message: format!("{error}."),
+        kind: ErrorKind::Runtime,
+    }
+}
+
+fn slice(text: &[char], range: Option<(usize, usize)>) -> Value {
+    match range {
+        Some((start, end)) => string(text[start..end].iter().collect::<String>()),
+        None => Value::Nil,
+    }
+}
+
+/// A match as a map of the matched text under `text`, its character positions under `start`
+/// and `end`, the capture groups under `groups`, and the named groups under `named`.
+fn match_map(regex: &Regex, text: &[char], captures: &Captures) -> Value {
+    let (start, end) = captures[0].unwrap_or_default();
+    let groups: Vec<Value> = captures[1..]
+        .iter()
+        .map(|range| slice(text, *range))
+        .collect();
+    let named: BTreeMap<String, Value> = regex
+        .names
+        .iter()
+        .map(|(name, index)| (name.clone(), slice(text, captures[*index])))
+        .collect();
+
+    let mut result: BTreeMap<String, Value> = BTreeMap::new();
+    result.insert("text".into(), slice(text, captures[0]));
+    result.insert("start".into(), Value::Number(start as f64));
+    result.insert("end".into(), Value::Number(end as f64));
+    result.insert("groups".into(), Value::List(Rc::new(RefCell::new(groups))));
+    result.insert("named".into(), Value::Map(Rc::new(RefCell::new(named))));
+
+    Value::Map(Rc::new(RefCell::new(result)))
+}
+
+/// The first match anywhere in the text, or nil if there is none.
+fn regex_match(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let regex = compile(interpreter, "regex_match", &arguments[0], token)?;
+    let text: Vec<char> = expect_string("regex_match", &arguments[1], token)?
+        .chars()
+        .collect();
+
+    match regex
+        .find_at(&text, 0)
+        .map_err(|error| too_complex(token, error))?
+    {
+        Some(captures) => Ok(match_map(&regex, &text, &captures)),
+        None => Ok(Value::Nil),
+    }
+}
+
+/// Every non-overlapping match, each a map like the ones `regex_match` returns.
+fn regex_find_all(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let regex = compile(interpreter, "regex_find_all", &arguments[0], token)?;
+    let text: Vec<char> = expect_string("regex_find_all", &arguments[1], token)?
+        .chars()
+        .collect();
+
+    let matches: Vec<Value> = regex
+        .find_all(&text)
+        .map_err(|error| too_complex(token, error))?
+        .iter()
+        .map(|captures| match_map(&regex, &text, captures))
+        .collect();
+
+    Ok(Value::List(Rc::new(RefCell::new(matches))))
+}
+
+/// Replaces every match. In the replacement, `$1` or `${1}` inserts a capture group,
+/// `${name}` a named group, `$0` the whole match and `$$` a dollar sign.
+fn regex_replace(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let regex = compile(interpreter, "regex_replace", &arguments[0], token)?;
+    let text: Vec<char> = expect_string("regex_replace", &arguments[1], token)?
+        .chars()
+        .collect();
+    let replacement = expect_string("regex_replace", &arguments[2], token)?;
+
+    let mut result = String::new();
+    let mut last = 0;
+
+    for captures in regex
+        .find_all(&text)
+        .map_err(|error| too_complex(token, error))?
+    {
+        let (start, end) = captures[0].unwrap_or_default();
+
+        result.extend(&text[last..start]);
+        expand(&mut result, &replacement, &regex, &captures, &text, token)?;
+        last = end;
+    }
+    result.extend(&text[last..]);
+
+    Ok(string(result))
+}
+
+fn expand(
+    result: &mut String,
+    replacement: &str,
+    regex: &Regex,
+    captures: &Captures,
+    text: &[char],
+    token: &Token,
+) -> Result<(), InterpreterError> {
+    let invalid = |reference: &str| InterpreterError {
+        token: Some(token.clone()),
+        message: format!("Invalid group reference '${reference}' in replacement."),
+        kind: ErrorKind::Runtime,
+    };
+
+    let mut characters = replacement.chars().peekable();
+
+    while let Some(character) = characters.next() {
+        if character != '$' {
+            result.push(character);
+            continue;
+        }
+
+        let reference: String = match characters.peek() {
+            Some('$') => {
+                characters.next();
+                result.push('$');
+                continue;
+            }
+            Some('{') => {
+                characters.next();
+                let name: String = characters.by_ref().take_while(|c| *c != '}').collect();
+                format!("{{{name}}}")
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let mut digits = String::new();
+                while let Some(digit) = characters.next_if(char::is_ascii_digit) {
+                    digits.push(digit);
+                }
+                digits
+            }
+            _ => return Err(invalid("")),
+        };
+
+        let index = match reference.strip_prefix('{') {
+            Some(name) => {
+                let name = name.trim_end_matches('}');
+                name.parse().ok().or_else(|| {
+                    regex
+                        .names
+                        .iter()
+                        .find(|(other, _)| other == name)
+                        .map(|(_, index)| *index)
+                })
+            }
+            None => reference.parse().ok(),
+        };
+
+        match index.filter(|index| *index < regex.groups) {
+            Some(index) => {
+                if let Some((start, end)) = captures[index] {
+                    result.extend(&text[start..end]);
+                }
+            }
+            None => return Err(invalid(&reference)),
+        }
+    }
+
+    Ok(())
+}
+
+/// The pieces of the text between matches.
+fn regex_split(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let regex = compile(interpreter, "regex_split", &arguments[0], token)?;
+    let text: Vec<char> = expect_string("regex_split", &arguments[1], token)?
+        .chars()
+        .collect();
+
+    let mut pieces: Vec<Value> = Vec::new();
+    let mut last = 0;
+
+    for captures in regex
+        .find_all(&text)
+        .map_err(|error| too_complex(token, error))?
+    {
+        let (start, end) = captures[0].unwrap_or_default();
+
+        // An empty match at either end of the text doesn't split off an empty piece.
+        if end == start && (start == 0 || start == text.len()) {
+            continue;
+        }
+
+        pieces.push(slice(&text, Some((last, start))));
+        last = end;
+    }
+    pieces.push(slice(&text, Some((last, text.len()))));
+
+    Ok(Value::List(Rc::new(RefCell::new(pieces))))
+}
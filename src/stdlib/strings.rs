@@ -0,0 +1,359 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    native::NativeFunction, parse_number, ErrorKind, EvaluateInterpreterResult, Interpreter,
+    InterpreterError, Token, Value,
+};
+
+use super::{argument_error, expect_integer, expect_string, string};
+
+pub const FUNCTIONS: &[NativeFunction] = &[
+    NativeFunction {
+        name: "len",
+        arity: 1,
+        max_arity: Some(1),
+        function: len,
+    },
+    NativeFunction {
+        name: "substr",
+        arity: 2,
+        max_arity: Some(3),
+        function: substr,
+    },
+    NativeFunction {
+        name: "index_of",
+        arity: 2,
+        max_arity: Some(2),
+        function: index_of,
+    },
+    NativeFunction {
+        name: "contains",
+        arity: 2,
+        max_arity: Some(2),
+        function: contains,
+    },
+    NativeFunction {
+        name: "starts_with",
+        arity: 2,
+        max_arity: Some(2),
+        function: starts_with,
+    },
+    NativeFunction {
+        name: "ends_with",
+        arity: 2,
+        max_arity: Some(2),
+        function: ends_with,
+    },
+    NativeFunction {
+        name: "upper",
+        arity: 1,
+        max_arity: Some(1),
+        function: upper,
+    },
+    NativeFunction {
+        name: "lower",
+        arity: 1,
+        max_arity: Some(1),
+        function: lower,
+    },
+    NativeFunction {
+        name: "trim",
+        arity: 1,
+        max_arity: Some(1),
+        function: trim,
+    },
+    NativeFunction {
+        name: "replace",
+        arity: 3,
+        max_arity: Some(3),
+        function: replace,
+    },
+    NativeFunction {
+        name: "split",
+        arity: 2,
+        max_arity: Some(2),
+        function: split,
+    },
+    NativeFunction {
+        name: "join",
+        arity: 2,
+        max_arity: Some(2),
+        function: join,
+    },
+    NativeFunction {
+        name: "repeat",
+        arity: 2,
+        max_arity: Some(2),
+        function: repeat,
+    },
+    NativeFunction {
+        name: "char_at",
+        arity: 2,
+        max_arity: Some(2),
+        function: char_at,
+    },
+    NativeFunction {
+        name: "ord",
+        arity: 1,
+        max_arity: Some(1),
+        function: ord,
+    },
+    NativeFunction {
+        name: "chr",
+        arity: 1,
+        max_arity: Some(1),
+        function: chr,
+    },
+    NativeFunction {
+        name: "str",
+        arity: 1,
+        max_arity: Some(1),
+        function: str,
+    },
+    NativeFunction {
+        name: "num",
+        arity: 1,
+        max_arity: Some(1),
+        function: num,
+    },
+];
+
+/// Checks that `index` is a character position within a string of `length` characters,
+/// where `length` itself is allowed if `inclusive`.
+fn check_position(
+    index: i64,
+    length: usize,
+    inclusive: bool,
+    token: &Token,
+) -> Result<usize, InterpreterError> {
+    let limit = if inclusive {
+        length as i64 + 1
+    } else {
+        length as i64
+    };
+
+    if index < 0 || index >= limit {
+        return Err(InterpreterError {
+            token: Some(token.clone()),
+            message: format!("Index {index} is out of bounds for length {length}."),
+            kind: ErrorKind::Runtime,
+        });
+    }
+
+    Ok(index as usize)
+}
+
+/// The number of characters in a string, or of elements in a list or map.
+fn len(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    let length = match &arguments[0] {
+        Value::String(string) => string.chars().count(),
+        Value::List(list) => list.borrow().len(),
+        Value::Map(map) => map.borrow().len(),
+        _ => return Err(argument_error("len", "a string, list or map", token)),
+    };
+
+    Ok(Value::Number(length as f64))
+}
+
+/// The characters from `start` up to, but not including, `end` (or the end of the string).
+fn substr(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    let characters: Vec<char> = expect_string("substr", &arguments[0], token)?
+        .chars()
+        .collect();
+
+    let start = expect_integer("substr", &arguments[1], token)?;
+    let start = check_position(start, characters.len(), true, token)?;
+
+    let end = match arguments.get(2) {
+        Some(end) => {
+            let end = expect_integer("substr", end, token)?;
+            check_position(end, characters.len(), true, token)?.max(start)
+        }
+        None => characters.len(),
+    };
+
+    Ok(string(characters[start..end].iter().collect::<String>()))
+}
+
+/// The character position of the first occurrence of `needle`, or -1 if there is none.
+fn index_of(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let haystack = expect_string("index_of", &arguments[0], token)?;
+    let needle = expect_string("index_of", &arguments[1], token)?;
+
+    let index = match haystack.find(needle.as_str()) {
+        Some(offset) => haystack[..offset].chars().count() as f64,
+        None => -1.0,
+    };
+
+    Ok(Value::Number(index))
+}
+
+fn contains(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let haystack = expect_string("contains", &arguments[0], token)?;
+    let needle = expect_string("contains", &arguments[1], token)?;
+
+    Ok(Value::Boolean(haystack.contains(needle.as_str())))
+}
+
+fn starts_with(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let string = expect_string("starts_with", &arguments[0], token)?;
+    let prefix = expect_string("starts_with", &arguments[1], token)?;
+
+    Ok(Value::Boolean(string.starts_with(prefix.as_str())))
+}
+
+fn ends_with(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let string = expect_string("ends_with", &arguments[0], token)?;
+    let suffix = expect_string("ends_with", &arguments[1], token)?;
+
+    Ok(Value::Boolean(string.ends_with(suffix.as_str())))
+}
+
+fn upper(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    Ok(string(
+        expect_string("upper", &arguments[0], token)?.to_uppercase(),
+    ))
+}
+
+fn lower(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    Ok(string(
+        expect_string("lower", &arguments[0], token)?.to_lowercase(),
+    ))
+}
+
+fn trim(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    Ok(string(expect_string("trim", &arguments[0], token)?.trim()))
+}
+
+/// Replaces every occurrence of `from` with `to`.
+fn replace(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    let subject = expect_string("replace", &arguments[0], token)?;
+    let from = expect_string("replace", &arguments[1], token)?;
+    let to = expect_string("replace", &arguments[2], token)?;
+
+    if from.is_empty() {
+        return Err(argument_error("replace", "a non-empty pattern", token));
+    }
+
+    Ok(string(subject.replace(from.as_str(), &to)))
+}
+
+/// Splits on every occurrence of `separator`, or into characters if it is empty.
+fn split(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    let subject = expect_string("split", &arguments[0], token)?;
+    let separator = expect_string("split", &arguments[1], token)?;
+
+    let parts: Vec<Value> = if separator.is_empty() {
+        subject
+            .chars()
+            .map(|character| string(character.to_string()))
+            .collect()
+    } else {
+        subject.split(separator.as_str()).map(string).collect()
+    };
+
+    Ok(Value::List(Rc::new(RefCell::new(parts))))
+}
+
+/// Joins the elements of a list, formatted as `print` would, with `separator` between them.
+fn join(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let Value::List(list) = &arguments[0] else {
+        return Err(argument_error("join", "a list", token));
+    };
+    let separator = expect_string("join", &arguments[1], token)?;
+
+    let elements = list.borrow().clone();
+    let mut parts: Vec<String> = Vec::new();
+    for element in &elements {
+        parts.push(interpreter.stringify(element)?);
+    }
+
+    Ok(string(parts.join(&separator)))
+}
+
+/// The longest string `repeat` builds, in bytes.
+const MAX_REPEAT_LENGTH: usize = 1 << 28;
+
+fn repeat(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    let subject = expect_string("repeat", &arguments[0], token)?;
+    let count = expect_integer("repeat", &arguments[1], token)?;
+
+    if count < 0 {
+        return Err(argument_error("repeat", "a non-negative integer", token));
+    }
+
+    let too_large = subject
+        .len()
+        .checked_mul(count as usize)
+        .is_none_or(|length| length > MAX_REPEAT_LENGTH);
+    if too_large {
+        let expected = format!("a count giving at most {MAX_REPEAT_LENGTH} bytes");
+        return Err(argument_error("repeat", &expected, token));
+    }
+
+    Ok(string(subject.repeat(count as usize)))
+}
+
+fn char_at(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    let subject = expect_string("char_at", &arguments[0], token)?;
+    let index = expect_integer("char_at", &arguments[1], token)?;
+    let index = check_position(index, subject.chars().count(), false, token)?;
+
+    Ok(string(subject.chars().nth(index).unwrap_or_default()))
+}
+
+/// The code point of a single-character string.
+fn ord(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    let subject = expect_string("ord", &arguments[0], token)?;
+
+    let mut characters = subject.chars();
+    match (characters.next(), characters.next()) {
+        (Some(character), None) => Ok(Value::Number(character as u32 as f64)),
+        _ => Err(argument_error("ord", "a single character", token)),
+    }
+}
+
+/// The single-character string for a code point.
+fn chr(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    let code = expect_integer("chr", &arguments[0], token)?;
+
+    match u32::try_from(code).ok().and_then(char::from_u32) {
+        Some(character) => Ok(string(character.to_string())),
+        None => Err(argument_error("chr", "a valid code point", token)),
+    }
+}
+
+fn str(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    _: &Token,
+) -> EvaluateInterpreterResult {
+    Ok(string(interpreter.stringify(&arguments[0])?))
+}
+
+/// Parses a string using the rules for number literals, returning nil if it isn't one.
+fn num(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    let subject = expect_string("num", &arguments[0], token)?;
+
+    Ok(parse_number(&subject).map_or(Value::Nil, Value::Number))
+}
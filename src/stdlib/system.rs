@@ -0,0 +1,89 @@
+use std::env;
+
+use crate::{
+    native::NativeFunction, ErrorKind, EvaluateInterpreterResult, Interpreter, InterpreterError,
+    Token, Value,
+};
+
+use super::{argument_error, denied, expect_integer, expect_string, string};
+
+pub const FUNCTIONS: &[NativeFunction] = &[
+    NativeFunction {
+        name: "env",
+        arity: 1,
+        max_arity: Some(1),
+        function: env,
+    },
+    NativeFunction {
+        name: "set_env",
+        arity: 2,
+        max_arity: Some(2),
+        function: set_env,
+    },
+    NativeFunction {
+        name: "exit",
+        arity: 0,
+        max_arity: Some(1),
+        function: exit,
+    },
+];
+
+fn check_environment(interpreter: &Interpreter, token: &Token) -> Result<(), InterpreterError> {
+    match interpreter.capabilities.environment {
+        true => Ok(()),
+        false => Err(denied("the environment", token)),
+    }
+}
+
+/// The value of an environment variable, or nil if it isn't set.
+fn env(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    check_environment(interpreter, token)?;
+    let name = expect_string("env", &arguments[0], token)?;
+
+    Ok(env::var(name.as_str()).map_or(Value::Nil, string))
+}
+
+fn set_env(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    check_environment(interpreter, token)?;
+    let name = expect_string("set_env", &arguments[0], token)?;
+    let value = expect_string("set_env", &arguments[1], token)?;
+
+    if name.is_empty() || name.contains(['=', '\0']) || value.contains('\0') {
+        return Err(argument_error(
+            "set_env",
+            "a valid variable name and value",
+            token,
+        ));
+    }
+
+    env::set_var(name.as_str(), value.as_str());
+
+    Ok(Value::Nil)
+}
+
+/// Ends the script with the given status code (0 by default). The exit unwinds through
+/// every `try`, so `finally` blocks still run, and leaves the process to the caller.
+fn exit(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    let code = match arguments.first() {
+        Some(code) => expect_integer("exit", code, token)?,
+        None => 0,
+    };
+
+    let Ok(code) = i32::try_from(code) else {
+        return Err(argument_error("exit", "a valid status code", token));
+    };
+
+    Err(InterpreterError {
+        token: Some(token.clone()),
+        message: format!("Exited with code {code}."),
+        kind: ErrorKind::Exit(code),
+    })
+}
@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+use crate::{native::NativeFunction, EvaluateInterpreterResult, Interpreter, Token, Value};
+
+use super::{argument_error, expect_number};
+
+pub const FUNCTIONS: &[NativeFunction] = &[
+    NativeFunction {
+        name: "clock_ms",
+        arity: 0,
+        max_arity: Some(0),
+        function: clock_ms,
+    },
+    NativeFunction {
+        name: "clock_ns",
+        arity: 0,
+        max_arity: Some(0),
+        function: clock_ns,
+    },
+    NativeFunction {
+        name: "monotonic",
+        arity: 0,
+        max_arity: Some(0),
+        function: monotonic,
+    },
+    NativeFunction {
+        name: "sleep",
+        arity: 1,
+        max_arity: Some(1),
+        function: sleep,
+    },
+];
+
+/// Milliseconds since the Unix epoch.
+fn clock_ms(interpreter: &mut Interpreter, _: Vec<Value>, _: &Token) -> EvaluateInterpreterResult {
+    Ok(Value::Number(interpreter.clock.now().as_secs_f64() * 1e3))
+}
+
+/// Nanoseconds since the Unix epoch, which a number can only hold to about a microsecond.
+fn clock_ns(interpreter: &mut Interpreter, _: Vec<Value>, _: &Token) -> EvaluateInterpreterResult {
+    Ok(Value::Number(interpreter.clock.now().as_nanos() as f64))
+}
+
+/// Seconds since the interpreter started, unaffected by changes to the system clock.
+fn monotonic(interpreter: &mut Interpreter, _: Vec<Value>, _: &Token) -> EvaluateInterpreterResult {
+    Ok(Value::Number(interpreter.clock.monotonic().as_secs_f64()))
+}
+
+/// Pauses for the given number of milliseconds.
+fn sleep(
+    interpreter: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let milliseconds = expect_number("sleep", &arguments[0], token)?;
+
+    let duration = Duration::try_from_secs_f64(milliseconds / 1e3)
+        .map_err(|_| argument_error("sleep", "a non-negative number", token))?;
+    interpreter.clock.sleep(duration);
+
+    Ok(Value::Nil)
+}
@@ -0,0 +1,84 @@
+use std::rc::Rc;
+
+use crate::{native::NativeFunction, EvaluateInterpreterResult, Interpreter, Token, Value};
+
+use super::{argument_error, expect_function};
+
+pub const FUNCTIONS: &[NativeFunction] = &[
+    NativeFunction {
+        name: "type",
+        arity: 1,
+        max_arity: Some(1),
+        function: type_,
+    },
+    NativeFunction {
+        name: "is_callable",
+        arity: 1,
+        max_arity: Some(1),
+        function: is_callable,
+    },
+    NativeFunction {
+        name: "arity",
+        arity: 1,
+        max_arity: Some(1),
+        function: arity,
+    },
+    NativeFunction {
+        name: "name",
+        arity: 1,
+        max_arity: Some(1),
+        function: name,
+    },
+    NativeFunction {
+        name: "implements",
+        arity: 2,
+        max_arity: Some(2),
+        function: implements,
+    },
+];
+
+fn type_(_: &mut Interpreter, arguments: Vec<Value>, _: &Token) -> EvaluateInterpreterResult {
+    Ok(Value::String(Rc::new(arguments[0].type_name().into())))
+}
+
+fn is_callable(_: &mut Interpreter, arguments: Vec<Value>, _: &Token) -> EvaluateInterpreterResult {
+    Ok(Value::Boolean(matches!(
+        arguments[0],
+        Value::Function(_) | Value::Class(_)
+    )))
+}
+
+/// The number of arguments `f` requires; defaulted and rest parameters are not counted.
+fn arity(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    let function = expect_function("arity", &arguments[0], token)?;
+    let arity = function.borrow().arity();
+
+    Ok(Value::Number(arity as f64))
+}
+
+fn name(_: &mut Interpreter, arguments: Vec<Value>, token: &Token) -> EvaluateInterpreterResult {
+    let function = expect_function("name", &arguments[0], token)?;
+    let name = function.borrow().as_str();
+
+    Ok(Value::String(Rc::new(name)))
+}
+
+/// Whether an instance's class, or a class itself, was declared `with` the trait, directly
+/// or through a superclass.
+fn implements(
+    _: &mut Interpreter,
+    arguments: Vec<Value>,
+    token: &Token,
+) -> EvaluateInterpreterResult {
+    let Value::Trait(r#trait) = &arguments[1] else {
+        return Err(argument_error("implements", "a trait", token));
+    };
+
+    let implements = match &arguments[0] {
+        Value::Instance(instance) => instance.class.implements(r#trait),
+        Value::Class(class) => class.implements(r#trait),
+        _ => false,
+    };
+
+    Ok(Value::Boolean(implements))
+}
@@ -0,0 +1,139 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{Capabilities, Interpreter, InterpreterError, Parser, Scanner, Statement, Token};
+
+/// The result of running one `test_*` function.
+#[derive(Debug)]
+pub struct TestOutcome {
+    pub file: PathBuf,
+    pub name: String,
+    /// The failure message, or `None` if the test passed.
+    pub failure: Option<String>,
+}
+
+/// Collects the `.lox` files under `paths`, descending into directories, in sorted order.
+pub fn discover_tests(paths: &[PathBuf]) -> io::Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = Vec::new();
+
+    for path in paths {
+        if path.is_dir() {
+            let mut entries = fs::read_dir(path)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<io::Result<Vec<PathBuf>>>()?;
+            entries.sort();
+
+            let entries = entries
+                .into_iter()
+                .filter(|entry| entry.is_dir() || entry.extension().is_some_and(|e| e == "lox"))
+                .collect::<Vec<PathBuf>>();
+
+            files.extend(discover_tests(&entries)?);
+        } else {
+            files.push(path.clone());
+        }
+    }
+
+    Ok(files)
+}
+
+/// Runs every top-level `test_*` function in `file`, each in a fresh interpreter prepared by
+/// `setup` that has executed the rest of the file first, so a test can't see what another
+/// one changed. Only the first execution of the file prints its output.
+///
+/// A file that can't be read, scanned, parsed or executed is reported as a single failed
+/// test named after the file.
+pub fn run_tests(file: &Path, setup: impl Fn(&mut Interpreter)) -> Vec<TestOutcome> {
+    let failed = |message: String| {
+        vec![TestOutcome {
+            file: file.to_path_buf(),
+            name: file.display().to_string(),
+            failure: Some(message),
+        }]
+    };
+
+    let source = match fs::read_to_string(file) {
+        Ok(source) => source,
+        Err(error) => return failed(format!("Could not read file: {error}.")),
+    };
+
+    let mut scanner = Scanner::new(source);
+    let tokens = scanner.scan_tokens();
+
+    if scanner.had_error {
+        return failed("Could not scan file.".into());
+    }
+
+    let statements = match Parser::new(tokens).parse() {
+        Ok(statements) => statements,
+        Err(error) => return failed(error.to_string()),
+    };
+
+    let tests = statements
+        .iter()
+        .filter_map(|statement| match statement {
+            Statement::Function { name, .. } if name.lexeme.starts_with("test_") => {
+                Some(name.clone())
+            }
+            _ => None,
+        })
+        .collect::<Vec<Token>>();
+
+    let prepare = |quiet: bool| {
+        let mut interpreter = Interpreter::new();
+        interpreter.current_file = Some(file.to_path_buf());
+        interpreter.capabilities = Capabilities::all();
+        setup(&mut interpreter);
+
+        interpreter.quiet = quiet;
+        interpreter.interpret(statements.clone())?;
+        interpreter.quiet = false;
+
+        Ok::<Interpreter, InterpreterError>(interpreter)
+    };
+
+    let mut first = match prepare(false) {
+        Ok(interpreter) => Some(interpreter),
+        Err(error) => return failed(describe(error)),
+    };
+
+    tests
+        .into_iter()
+        .map(|name| {
+            let interpreter = match first.take() {
+                Some(interpreter) => Ok(interpreter),
+                None => prepare(true),
+            };
+
+            let failure = match interpreter {
+                Ok(mut interpreter) => run_test(&mut interpreter, name.clone()).err(),
+                Err(error) => Some(describe(error)),
+            };
+
+            TestOutcome {
+                file: file.to_path_buf(),
+                name: name.lexeme,
+                failure,
+            }
+        })
+        .collect()
+}
+
+fn run_test(interpreter: &mut Interpreter, name: Token) -> Result<(), String> {
+    let function = interpreter.globals.get(&name).map_err(describe)?;
+    interpreter
+        .call_value(function, Vec::new(), name)
+        .map_err(describe)?;
+
+    Ok(())
+}
+
+/// The failure message for an error, with the line it was raised on.
+fn describe(error: InterpreterError) -> String {
+    match &error.token {
+        Some(token) => format!("{error} [line {}]", token.line),
+        None => error.to_string(),
+    }
+}
@@ -1,6 +1,12 @@
-use crate::{Callable, Literal};
+use crate::{
+    Callable, Generator, Literal, LoxClass, LoxEnum, LoxInstance, LoxModule, LoxTrait, LoxVariant,
+};
 use core::fmt;
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    rc::Rc,
+};
 
 #[derive(Debug, Clone)]
 pub enum Value {
@@ -9,6 +15,35 @@ pub enum Value {
     String(Rc<String>),
     Number(f64),
     Function(Rc<RefCell<dyn Callable>>),
+    Error(Rc<LoxError>),
+    Module(Rc<LoxModule>),
+    List(Rc<RefCell<Vec<Value>>>),
+    Map(Rc<RefCell<BTreeMap<String, Value>>>),
+    Range(Range),
+    Generator(Rc<RefCell<Generator>>),
+    Enum(Rc<LoxEnum>),
+    Variant(Rc<LoxVariant>),
+    Class(Rc<LoxClass>),
+    Instance(Rc<LoxInstance>),
+    Trait(Rc<LoxTrait>),
+}
+
+/// The numbers from `start` up to, but not including, `end`, `step` apart. A negative step
+/// counts down.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Range {
+    pub start: f64,
+    pub end: f64,
+    pub step: f64,
+}
+
+/// A runtime error caught by a `catch` clause.
+#[derive(Debug, PartialEq)]
+pub struct LoxError {
+    pub message: String,
+    pub line: Option<usize>,
+    /// Whether a failed `assert` raised it, rather than the interpreter.
+    pub assertion: bool,
 }
 
 impl From<Literal> for Value {
@@ -24,19 +59,119 @@ impl From<Literal> for Value {
 
 impl PartialEq for Value {
     fn eq(&self, other: &Self) -> bool {
+        self.equals(other, &mut Vec::new())
+    }
+}
+
+impl Value {
+    /// Compares lists and maps element by element, tracking the pairs being compared in
+    /// `visiting`. A pair met again inside itself is compared by pointer, so values that
+    /// contain themselves can't recurse for ever.
+    fn equals(&self, other: &Self, visiting: &mut Vec<(*const (), *const ())>) -> bool {
         match (self, other) {
             (Value::Nil, Value::Nil) => true,
             (Value::Boolean(a), Value::Boolean(b)) => a == b,
             (Value::String(a), Value::String(b)) => a == b,
             (Value::Number(a), Value::Number(b)) => a == b,
             (Value::Function(a), Value::Function(b)) => std::ptr::addr_eq(a.as_ptr(), b.as_ptr()),
+            (Value::Error(a), Value::Error(b)) => Rc::ptr_eq(a, b),
+            (Value::Module(a), Value::Module(b)) => Rc::ptr_eq(a, b),
+            (Value::List(a), Value::List(b)) => {
+                let pair = (Rc::as_ptr(a) as *const (), Rc::as_ptr(b) as *const ());
+                if Rc::ptr_eq(a, b) || visiting.contains(&pair) {
+                    return Rc::ptr_eq(a, b);
+                }
+
+                visiting.push(pair);
+                let equal = Value::all_equal(&a.borrow(), &b.borrow(), visiting);
+                visiting.pop();
+
+                equal
+            }
+            (Value::Map(a), Value::Map(b)) => {
+                let pair = (Rc::as_ptr(a) as *const (), Rc::as_ptr(b) as *const ());
+                if Rc::ptr_eq(a, b) || visiting.contains(&pair) {
+                    return Rc::ptr_eq(a, b);
+                }
+
+                visiting.push(pair);
+                let (a, b) = (a.borrow(), b.borrow());
+                let equal = a.len() == b.len()
+                    && a.iter()
+                        .zip(b.iter())
+                        .all(|((a_key, a), (b_key, b))| a_key == b_key && a.equals(b, visiting));
+                visiting.pop();
+
+                equal
+            }
+            (Value::Range(a), Value::Range(b)) => a == b,
+            (Value::Generator(a), Value::Generator(b)) => Rc::ptr_eq(a, b),
+            (Value::Enum(a), Value::Enum(b)) => Rc::ptr_eq(a, b),
+            (Value::Variant(a), Value::Variant(b)) => {
+                Rc::ptr_eq(&a.enumeration, &b.enumeration)
+                    && a.tag == b.tag
+                    && Value::all_equal(&a.values, &b.values, visiting)
+            }
+            (Value::Class(a), Value::Class(b)) => Rc::ptr_eq(a, b),
+            (Value::Instance(a), Value::Instance(b)) => Rc::ptr_eq(a, b),
+            (Value::Trait(a), Value::Trait(b)) => Rc::ptr_eq(a, b),
             _ => false,
         }
     }
-}
 
-impl fmt::Display for Value {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fn all_equal(a: &[Value], b: &[Value], visiting: &mut Vec<(*const (), *const ())>) -> bool {
+        a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.equals(b, visiting))
+    }
+
+    /// The name `type(x)` reports for this value.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Nil => "nil",
+            Value::Boolean(_) => "boolean",
+            Value::String(_) => "string",
+            Value::Number(_) => "number",
+            Value::Function(_) => "function",
+            Value::Error(_) => "error",
+            Value::Module(_) => "module",
+            Value::List(_) => "list",
+            Value::Map(_) => "map",
+            Value::Range(_) => "range",
+            Value::Generator(_) => "generator",
+            Value::Enum(_) => "enum",
+            Value::Variant(_) => "variant",
+            Value::Class(_) => "class",
+            Value::Instance(_) => "instance",
+            Value::Trait(_) => "trait",
+        }
+    }
+
+    /// The value's text, with the text in `instances` for the instances written within it.
+    pub fn to_string_with(&self, instances: &InstanceText) -> String {
+        WithInstances(self, instances).to_string()
+    }
+
+    /// Formats a value inside a list or map, where strings are quoted.
+    fn fmt_nested(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        visiting: &mut Vec<*const ()>,
+        instances: &InstanceText,
+    ) -> fmt::Result {
+        match self {
+            Value::String(value) => write!(f, "{value:?}"),
+            value => value.fmt_visiting(f, visiting, instances),
+        }
+    }
+
+    /// Formats the value, tracking the lists and maps being written in `visiting` so that
+    /// one inside itself is written as `[...]` or `{...}`, and writing the instances in
+    /// `instances` as their text there.
+    fn fmt_visiting(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        visiting: &mut Vec<*const ()>,
+        instances: &InstanceText,
+    ) -> fmt::Result {
         match self {
             Value::Nil => write!(f, "nil"),
             Value::Boolean(value) => {
@@ -49,6 +184,101 @@ impl fmt::Display for Value {
             Value::String(value) => write!(f, "{}", *value),
             Value::Number(value) => write!(f, "{value}"),
             Value::Function(value) => write!(f, "{}", value.borrow().as_str()),
+            Value::Error(value) => write!(f, "{}", value.message),
+            Value::Module(value) => write!(f, "<module {}>", value.path.display()),
+            Value::List(values) => {
+                let pointer = Rc::as_ptr(values) as *const ();
+                if visiting.contains(&pointer) {
+                    return write!(f, "[...]");
+                }
+
+                visiting.push(pointer);
+                write!(f, "[")?;
+                for (index, value) in values.borrow().iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+
+                    value.fmt_nested(f, visiting, instances)?;
+                }
+                visiting.pop();
+
+                write!(f, "]")
+            }
+            Value::Map(entries) => {
+                let pointer = Rc::as_ptr(entries) as *const ();
+                if visiting.contains(&pointer) {
+                    return write!(f, "{{...}}");
+                }
+
+                visiting.push(pointer);
+                write!(f, "{{")?;
+                for (index, (key, value)) in entries.borrow().iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+
+                    write!(f, "{key:?}: ")?;
+                    value.fmt_nested(f, visiting, instances)?;
+                }
+                visiting.pop();
+
+                write!(f, "}}")
+            }
+            Value::Range(range) if range.step == 1.0 => {
+                write!(f, "{}..{}", range.start, range.end)
+            }
+            Value::Range(range) => {
+                write!(f, "range({}, {}, {})", range.start, range.end, range.step)
+            }
+            Value::Generator(generator) => match generator.try_borrow() {
+                Ok(generator) => write!(f, "<generator {}>", generator.name),
+                Err(_) => write!(f, "<generator>"),
+            },
+            Value::Enum(enumeration) => write!(f, "<enum {}>", enumeration.name),
+            Value::Variant(variant) => {
+                write!(f, "{}.{}", variant.enumeration.name, variant.tag)?;
+
+                if !variant.values.is_empty() {
+                    write!(f, "(")?;
+                    for (index, value) in variant.values.iter().enumerate() {
+                        if index > 0 {
+                            write!(f, ", ")?;
+                        }
+
+                        value.fmt_nested(f, visiting, instances)?;
+                    }
+                    write!(f, ")")?;
+                }
+
+                Ok(())
+            }
+            Value::Class(class) => write!(f, "{}", class.name),
+            Value::Instance(instance) => {
+                match instances.get(&(Rc::as_ptr(instance) as *const ())) {
+                    Some(text) => write!(f, "{text}"),
+                    None => write!(f, "{} instance", instance.class.name),
+                }
+            }
+            Value::Trait(r#trait) => write!(f, "<trait {}>", r#trait.name),
         }
     }
 }
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_visiting(f, &mut Vec::new(), &InstanceText::new())
+    }
+}
+
+/// The text to write for particular instances, keyed by their address, such as the results
+/// of their `__str__` methods.
+pub type InstanceText = HashMap<*const (), String>;
+
+struct WithInstances<'a>(&'a Value, &'a InstanceText);
+
+impl fmt::Display for WithInstances<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_visiting(f, &mut Vec::new(), self.1)
+    }
+}
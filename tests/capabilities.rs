@@ -0,0 +1,85 @@
+//! The natives that reach outside the interpreter are denied unless its capabilities allow
+//! them. The CLI grants everything, so these run the interpreter as a library.
+
+use std::{env, path::PathBuf};
+
+use codecrafters_interpreter::{Capabilities, Interpreter, Parser, Scanner};
+
+/// Runs `source` and returns the message of the error it stopped with, if any.
+fn run(capabilities: Capabilities, source: &str) -> Option<String> {
+    let tokens = Scanner::new(source.into()).scan_tokens();
+    let statements = Parser::new(tokens).parse().unwrap();
+
+    let mut interpreter = Interpreter::new();
+    interpreter.capabilities = capabilities;
+
+    interpreter
+        .interpret(statements)
+        .err()
+        .map(|error| error.message)
+}
+
+fn fixtures() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/lox")
+}
+
+#[test]
+fn denies_everything_by_default() {
+    let denied = |source: &str, subject: &str| {
+        assert_eq!(
+            run(Capabilities::default(), source),
+            Some(format!("Access to '{subject}' is not allowed."))
+        );
+    };
+
+    denied("read_line();", "standard input");
+    denied("read_all_stdin();", "standard input");
+    denied("env(\"HOME\");", "the environment");
+    denied("set_env(\"X\", \"1\");", "the environment");
+    denied("read_file(\"Cargo.toml\");", "Cargo.toml");
+    denied("exists(\"Cargo.toml\");", "Cargo.toml");
+}
+
+#[test]
+fn allows_files_within_a_root() {
+    let capabilities = Capabilities {
+        file_roots: vec![fixtures()],
+        ..Capabilities::default()
+    };
+    let inside = fixtures().join("modules/greeting.lox");
+    let outside = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+
+    let source = format!("read_file(\"{}\");", inside.display());
+    assert_eq!(run(capabilities.clone(), &source), None);
+
+    let source = format!("read_file(\"{}\");", outside.display());
+    assert_eq!(
+        run(capabilities.clone(), &source),
+        Some(format!("Access to '{}' is not allowed.", outside.display()))
+    );
+
+    // A path that leaves the root through `..` is resolved before it is checked.
+    let escape = fixtures().join("../../Cargo.toml");
+    let source = format!("read_file(\"{}\");", escape.display());
+    assert_eq!(
+        run(capabilities, &source),
+        Some(format!("Access to '{}' is not allowed.", escape.display()))
+    );
+}
+
+#[test]
+fn imports_only_modules_within_a_root() {
+    let module = fixtures().join("modules/counter.lox");
+    let source = format!("import \"{}\" as counter;", module.display());
+
+    assert_eq!(
+        run(Capabilities::default(), &source),
+        Some(format!("Access to '{}' is not allowed.", module.display()))
+    );
+
+    let capabilities = Capabilities {
+        file_roots: vec![fixtures()],
+        ..Capabilities::default()
+    };
+    assert_eq!(run(capabilities, &source), None);
+}
@@ -0,0 +1,167 @@
+//! Runs each script in `tests/lox` and checks its output against the comments in it, in the
+//! style of the Crafting Interpreters test suite:
+//!
+//! - `// expect: text` expects the next line of standard output to be `text`.
+//! - `// expect runtime error: message` expects the script to stop with that error and exit
+//!   code 70.
+//! - `// expect error: message` expects the parser to reject the script with that error and
+//!   exit code 65.
+//! - `// expect warning: message` expects the parser to print that warning.
+//! - `// expect exit: code` expects the process to exit with `code`.
+//! - `// command: name` runs the script with `name` instead of the `run` command.
+//! - `// options: ...` passes options to the command, `// arguments: ...` passes arguments
+//!   to the script, and `// stdin: text` adds a line to its standard input.
+//!
+//! Scripts in subdirectories are only run when imported by a top-level script.
+
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+#[derive(Debug, Default)]
+struct Expectations {
+    output: Vec<String>,
+    runtime_error: Option<String>,
+    error: Option<String>,
+    warnings: Vec<String>,
+    exit: Option<i32>,
+    command: Option<String>,
+    options: Vec<String>,
+    arguments: Vec<String>,
+    stdin: String,
+}
+
+fn expectations(source: &str) -> Expectations {
+    let mut expectations = Expectations::default();
+
+    for line in source.lines() {
+        let Some((_, comment)) = line.split_once("// ") else {
+            continue;
+        };
+
+        if let Some(text) = comment.strip_prefix("expect: ") {
+            expectations.output.push(text.to_string());
+        } else if let Some(message) = comment.strip_prefix("expect runtime error: ") {
+            expectations.runtime_error = Some(message.to_string());
+        } else if let Some(message) = comment.strip_prefix("expect error: ") {
+            expectations.error = Some(message.to_string());
+        } else if let Some(message) = comment.strip_prefix("expect warning: ") {
+            expectations.warnings.push(message.to_string());
+        } else if let Some(code) = comment.strip_prefix("expect exit: ") {
+            expectations.exit = Some(code.parse().unwrap());
+        } else if let Some(command) = comment.strip_prefix("command: ") {
+            expectations.command = Some(command.to_string());
+        } else if let Some(options) = comment.strip_prefix("options: ") {
+            expectations
+                .options
+                .extend(options.split_whitespace().map(String::from));
+        } else if let Some(arguments) = comment.strip_prefix("arguments: ") {
+            expectations
+                .arguments
+                .extend(arguments.split_whitespace().map(String::from));
+        } else if let Some(text) = comment.strip_prefix("stdin: ") {
+            expectations.stdin.push_str(text);
+            expectations.stdin.push('\n');
+        }
+    }
+
+    expectations
+}
+
+/// Runs the script from its own directory, so paths in its output are relative to it.
+fn run(directory: &Path, path: &Path) -> Result<(), String> {
+    let source = fs::read_to_string(path).map_err(|error| error.to_string())?;
+    let expectations = expectations(&source);
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_codecrafters_interpreter"))
+        .current_dir(directory)
+        .arg(expectations.command.as_deref().unwrap_or("run"))
+        .args(&expectations.options)
+        .arg(path.strip_prefix(directory).unwrap())
+        .args(&expectations.arguments)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| error.to_string())?;
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(expectations.stdin.as_bytes())
+        .map_err(|error| error.to_string())?;
+
+    let output = child
+        .wait_with_output()
+        .map_err(|error| error.to_string())?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    let actual: Vec<&str> = stdout.lines().collect();
+    if actual != expectations.output {
+        return Err(format!(
+            "expected output {:#?}\nbut got {:#?}\nstderr: {stderr}",
+            expectations.output, actual
+        ));
+    }
+
+    let (code, message) = match (&expectations.runtime_error, &expectations.error) {
+        (Some(message), _) => (70, Some(message)),
+        (None, Some(message)) => (65, Some(message)),
+        (None, None) => (expectations.exit.unwrap_or(0), None),
+    };
+
+    if output.status.code() != Some(code) {
+        return Err(format!(
+            "expected exit code {code} but got {:?}\nstderr: {stderr}",
+            output.status.code()
+        ));
+    }
+
+    if let Some(message) = message {
+        if !stderr.lines().any(|line| line == message) {
+            return Err(format!("expected error {message:?}\nbut got {stderr:?}"));
+        }
+    }
+
+    for warning in &expectations.warnings {
+        if !stderr.lines().any(|line| line == warning) {
+            return Err(format!("expected warning {warning:?}\nbut got {stderr:?}"));
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn scripts() {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/lox");
+
+    let mut scripts: Vec<PathBuf> = fs::read_dir(&directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "lox"))
+        .collect();
+    scripts.sort();
+
+    let failures: Vec<String> = scripts
+        .iter()
+        .filter_map(|script| {
+            run(&directory, script)
+                .err()
+                .map(|failure| format!("{}: {failure}", script.display()))
+        })
+        .collect();
+
+    assert!(
+        failures.is_empty(),
+        "{} of {} scripts failed:\n\n{}",
+        failures.len(),
+        scripts.len(),
+        failures.join("\n\n")
+    );
+}